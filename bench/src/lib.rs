@@ -0,0 +1,48 @@
+//! Test program used to measure the compute units consumed by each of the
+//! latest round paths. The first byte of instruction data selects the path,
+//! and the feed is expected as the first account.
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use std::hint::black_box;
+
+/// Selects `sclink::latest_round_data`
+pub const LATEST_ROUND_DATA: u8 = 0;
+/// Selects `sclink::latest_round_fast`
+pub const LATEST_ROUND_FAST: u8 = 4;
+/// Selects `sclink::latest_round_data_with_decimals`
+pub const LATEST_ROUND_DATA_WITH_DECIMALS: u8 = 1;
+/// Selects `sclink::latest_round_data_with_decimals2`
+pub const LATEST_ROUND_DATA_WITH_DECIMALS2: u8 = 2;
+/// Does nothing, used to subtract the fixed cost of invoking the program
+pub const BASELINE: u8 = 3;
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let feed = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    match data.first().copied() {
+        Some(LATEST_ROUND_DATA) => {
+            black_box(sclink::latest_round_data(feed)?);
+        }
+        Some(LATEST_ROUND_FAST) => {
+            black_box(sclink::latest_round_fast(feed)?);
+        }
+        Some(LATEST_ROUND_DATA_WITH_DECIMALS) => {
+            black_box(sclink::latest_round_data_with_decimals(feed)?);
+        }
+        Some(LATEST_ROUND_DATA_WITH_DECIMALS2) => {
+            black_box(sclink::latest_round_data_with_decimals2(feed)?);
+        }
+        Some(BASELINE) => {}
+        _ => return Err(ProgramError::InvalidInstructionData),
+    }
+    Ok(())
+}
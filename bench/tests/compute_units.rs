@@ -0,0 +1,106 @@
+//! Reports the compute units consumed by each latest round path.
+//!
+//! Run with `cargo test-sbf --manifest-path bench/Cargo.toml -- --nocapture`.
+#![cfg(feature = "test-sbf")]
+
+use sclink::store::Transmission;
+use sclink::test_utils::FeedBuilder;
+use sclink_bench::{
+    BASELINE, LATEST_ROUND_DATA, LATEST_ROUND_DATA_WITH_DECIMALS, LATEST_ROUND_DATA_WITH_DECIMALS2,
+    LATEST_ROUND_FAST,
+};
+use solana_program_test::{tokio, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signer,
+    transaction::Transaction,
+};
+
+#[tokio::test]
+async fn latest_round_compute_units() {
+    let program_id = Pubkey::new_unique();
+    let feed = Pubkey::new_unique();
+
+    // sized like a mainnet feed, 86400 live and 100 historical rounds with a granularity of
+    // 30, so the account copies are representative
+    let data = (1..=86400_u32)
+        .fold(
+            FeedBuilder::new()
+                .granularity(30)
+                .live_length(86400)
+                .historical_length(100),
+            |builder, i| {
+                builder.insert_round(Transmission::new(
+                    100_000 + u64::from(i),
+                    1_650_000_000 + i,
+                    4_000_000_000_000 + i128::from(i),
+                ))
+            },
+        )
+        .build();
+
+    let mut program_test = ProgramTest::new("sclink_bench", program_id, None);
+    program_test.prefer_bpf(true);
+    program_test.add_account(
+        feed,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: sclink::CHAINLINK_STORE_PROGRAM,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let mut consumed = Vec::new();
+    for (name, selector) in [
+        ("baseline", BASELINE),
+        ("latest_round_data", LATEST_ROUND_DATA),
+        ("latest_round_fast", LATEST_ROUND_FAST),
+        (
+            "latest_round_data_with_decimals",
+            LATEST_ROUND_DATA_WITH_DECIMALS,
+        ),
+        (
+            "latest_round_data_with_decimals2",
+            LATEST_ROUND_DATA_WITH_DECIMALS2,
+        ),
+    ] {
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &[selector],
+                vec![AccountMeta::new_readonly(feed, false)],
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        let simulation = banks_client.simulate_transaction(tx).await.unwrap();
+        simulation.result.unwrap().unwrap();
+        consumed.push((name, simulation.simulation_details.unwrap().units_consumed));
+    }
+
+    let baseline = consumed[0].1;
+    for (name, units) in consumed.iter().skip(1) {
+        println!(
+            "{:<34} {:>8} CU ({} over baseline)",
+            name,
+            units,
+            units - baseline
+        );
+    }
+
+    let units = |path| {
+        consumed
+            .iter()
+            .find(|(name, _)| *name == path)
+            .map(|(_, units)| *units)
+            .unwrap()
+    };
+    // latest_round_fast skips deserializing the header and the ring buffers
+    assert!(units("latest_round_fast") < units("latest_round_data"));
+}
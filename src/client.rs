@@ -0,0 +1,148 @@
+//! off-chain clients fetching a feed account over rpc and decoding it with the same
+//! functions used on chain
+use solana_client::client_error::ClientError;
+use solana_client::nonblocking::rpc_client::RpcClient as NonblockingRpcClient;
+use solana_client::rpc_client::RpcClient;
+use solana_program::account_info::{AccountInfo, IntoAccountInfo};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+
+use crate::Round;
+
+#[derive(Debug)]
+pub enum FeedClientError {
+    /// fetching the feed account failed
+    Rpc(Box<ClientError>),
+    /// the feed account couldn't be decoded
+    Program(ProgramError),
+}
+
+impl std::fmt::Display for FeedClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeedClientError::Rpc(err) => write!(f, "rpc error: {}", err),
+            FeedClientError::Program(err) => write!(f, "program error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for FeedClientError {}
+
+impl From<ClientError> for FeedClientError {
+    fn from(err: ClientError) -> Self {
+        FeedClientError::Rpc(Box::new(err))
+    }
+}
+
+impl From<ProgramError> for FeedClientError {
+    fn from(err: ProgramError) -> Self {
+        FeedClientError::Program(err)
+    }
+}
+
+/// wraps a fetched account in an `AccountInfo` and decodes it with `f`
+pub(crate) fn decode<T>(
+    feed: Pubkey,
+    account: Account,
+    f: impl FnOnce(&AccountInfo) -> Result<T, ProgramError>,
+) -> Result<T, FeedClientError> {
+    let mut account = (feed, account);
+    Ok(f(&account.into_account_info())?)
+}
+
+/// Reads a single feed through a blocking rpc client.
+pub struct ChainlinkFeedClient {
+    rpc: RpcClient,
+    feed: Pubkey,
+}
+
+impl ChainlinkFeedClient {
+    pub fn new(rpc: RpcClient, feed: Pubkey) -> Self {
+        ChainlinkFeedClient { rpc, feed }
+    }
+    fn read<T>(
+        &self,
+        f: impl FnOnce(&AccountInfo) -> Result<T, ProgramError>,
+    ) -> Result<T, FeedClientError> {
+        decode(self.feed, self.rpc.get_account(&self.feed)?, f)
+    }
+    pub fn latest_round_data(&self) -> Result<Round, FeedClientError> {
+        self.read(crate::latest_round_data)
+    }
+    pub fn decimals(&self) -> Result<u8, FeedClientError> {
+        self.read(crate::decimals)
+    }
+    pub fn description(&self) -> Result<String, FeedClientError> {
+        self.read(crate::description)
+    }
+}
+
+/// Same as [`ChainlinkFeedClient`] for the nonblocking rpc client.
+pub struct AsyncChainlinkFeedClient {
+    rpc: NonblockingRpcClient,
+    feed: Pubkey,
+}
+
+impl AsyncChainlinkFeedClient {
+    pub fn new(rpc: NonblockingRpcClient, feed: Pubkey) -> Self {
+        AsyncChainlinkFeedClient { rpc, feed }
+    }
+    async fn read<T>(
+        &self,
+        f: impl FnOnce(&AccountInfo) -> Result<T, ProgramError>,
+    ) -> Result<T, FeedClientError> {
+        decode(self.feed, self.rpc.get_account(&self.feed).await?, f)
+    }
+    pub async fn latest_round_data(&self) -> Result<Round, FeedClientError> {
+        self.read(crate::latest_round_data).await
+    }
+    pub async fn decimals(&self) -> Result<u8, FeedClientError> {
+        self.read(crate::decimals).await
+    }
+    pub async fn description(&self) -> Result<String, FeedClientError> {
+        self.read(crate::description).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::Transmission;
+    use crate::test_utils::FeedBuilder;
+    use crate::CHAINLINK_STORE_PROGRAM;
+
+    #[test]
+    fn decode_account() {
+        let account = Account {
+            lamports: 0,
+            data: FeedBuilder::new()
+                .insert_round(Transmission {
+                    answer: 42,
+                    ..Default::default()
+                })
+                .build(),
+            owner: CHAINLINK_STORE_PROGRAM,
+            executable: false,
+            rent_epoch: 0,
+        };
+        let feed = Pubkey::new_unique();
+        assert_eq!(
+            decode(feed, account.clone(), crate::latest_round_data)
+                .unwrap()
+                .answer,
+            42
+        );
+        assert!(matches!(
+            decode(
+                feed,
+                Account {
+                    owner: Pubkey::new_unique(),
+                    ..account
+                },
+                crate::decimals
+            ),
+            Err(FeedClientError::Program(_))
+        ));
+    }
+}
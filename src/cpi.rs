@@ -0,0 +1,119 @@
+//! queries through the store program itself rather than by reading the feed account,
+//! which keeps working across layout changes at the cost of a CPI
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::{get_return_data, invoke},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{Round, Scope};
+
+/// anchor's instruction discriminator, the first 8 bytes of `sha256("global:query")`
+pub const QUERY_DISCRIMINATOR: [u8; 8] = [39, 251, 130, 159, 46, 136, 164, 169];
+
+/// Builds the store program's query instruction. Only the scopes understood by the store
+/// program are accepted, the others are implemented by sclink on top of the account data.
+pub fn query_instruction(
+    store_program: &Pubkey,
+    feed: &Pubkey,
+    scope: Scope,
+) -> Result<Instruction, ProgramError> {
+    if !matches!(
+        scope,
+        Scope::Version
+            | Scope::Decimals
+            | Scope::Description
+            | Scope::RoundData { .. }
+            | Scope::LatestRoundData
+            | Scope::Aggregator
+    ) {
+        msg!("scope not supported by the store program");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut data = QUERY_DISCRIMINATOR.to_vec();
+    scope.serialize(&mut data)?;
+    Ok(Instruction {
+        program_id: *store_program,
+        accounts: vec![AccountMeta::new_readonly(*feed, false)],
+        data,
+    })
+}
+
+/// Invokes the store program's query instruction and returns its return data, which is
+/// borsh encoded as defined by the store program. Note that unlike [`crate::query`], the
+/// description is returned as a length prefixed string.
+pub fn query_via_program<'info>(
+    store_program: &AccountInfo<'info>,
+    feed: &AccountInfo<'info>,
+    scope: Scope,
+) -> Result<Vec<u8>, ProgramError> {
+    let instruction = query_instruction(store_program.key, feed.key, scope)?;
+    invoke(&instruction, &[feed.clone(), store_program.clone()])?;
+    match get_return_data() {
+        Some((program_id, data)) if program_id == *store_program.key => Ok(data),
+        _ => {
+            msg!("missing return data");
+            Err(ProgramError::InvalidAccountData)
+        }
+    }
+}
+
+/// Returns round data for the latest round, as reported by the store program.
+pub fn latest_round_data<'info>(
+    store_program: &AccountInfo<'info>,
+    feed: &AccountInfo<'info>,
+) -> Result<Round, ProgramError> {
+    Ok(Round::deserialize(
+        &mut &query_via_program(store_program, feed, Scope::LatestRoundData)?[..],
+    )?)
+}
+
+/// Returns round data for the given round, as reported by the store program.
+pub fn round_data<'info>(
+    store_program: &AccountInfo<'info>,
+    feed: &AccountInfo<'info>,
+    round_id: u32,
+) -> Result<Round, ProgramError> {
+    Ok(Round::deserialize(
+        &mut &query_via_program(store_program, feed, Scope::RoundData { round_id })?[..],
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_instruction_encoding() {
+        assert_eq!(
+            QUERY_DISCRIMINATOR,
+            solana_program::hash::hash(b"global:query").to_bytes()[..8]
+        );
+        let store_program = Pubkey::new_unique();
+        let feed = Pubkey::new_unique();
+        let instruction =
+            query_instruction(&store_program, &feed, Scope::RoundData { round_id: 7 }).unwrap();
+        assert_eq!(instruction.program_id, store_program);
+        assert_eq!(
+            instruction.accounts,
+            vec![AccountMeta::new_readonly(feed, false)]
+        );
+        // the scope variant index followed by the round id
+        assert_eq!(instruction.data[..8], QUERY_DISCRIMINATOR);
+        assert_eq!(instruction.data[8..], [3, 7, 0, 0, 0]);
+        assert_eq!(
+            query_instruction(&store_program, &feed, Scope::LatestRoundData)
+                .unwrap()
+                .data[8..],
+            [4]
+        );
+        assert_eq!(
+            query_instruction(&store_program, &feed, Scope::Owner).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use solana_program::program_error::ProgramError;
+
+/// Errors returned by sclink, surfaced as `ProgramError::Custom` with the
+/// discriminant as the error code so they can be told apart in transaction logs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub enum SclinkError {
+    /// the account isn't owned by the chainlink store program
+    InvalidOwner = 0,
+    /// the feed version isn't supported
+    BadVersion = 1,
+    /// the feed hasn't received any rounds yet
+    NoRounds = 2,
+    /// the account data is too small to hold the header, or the ring buffers are
+    /// malformed. buffers shorter than the declared live length are reported as
+    /// `ProgramError::AccountDataTooSmall` instead
+    TruncatedAccount = 3,
+    /// the account data couldn't be read as transmissions
+    CastFailed = 4,
+    /// the latest round is older than the accepted age
+    StalePrice = 5,
+}
+
+impl From<SclinkError> for ProgramError {
+    fn from(error: SclinkError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
@@ -1,28 +1,115 @@
 //! a lightweight client for querying chainlink pricefeeds, based on commit 72a857f37516a4202431156036cb93e2b6a8d9b3
 //! from https://github.com/smartcontractkit/chainlink-solana
+//!
+//! # no_std
+//!
+//! sclink can't be built for `no_std` targets yet, as `solana-program` and `borsh` 0.9
+//! both depend on `std`. Without any features those and `bytemuck` are the only
+//! dependencies, and within the crate itself only an allocator is needed on chain:
+//! - every [`Scope`] passed to [`query`] returns its result as an allocated `Vec<u8>`,
+//!   and so do the typed wrappers built on top of it
+//! - [`latest_round_fast`] and [`store::with_store_readonly`] avoid copying the ring
+//!   buffers, the latter only when the account data is aligned for `Transmission`
+//! - only the rpc helpers in `client` and `to_csv` are gated behind the `client` feature,
+//!   the stream behind `stream` and the test helpers behind `test-utils`
+//! - `RoundWithDecimals::format_answer`, `latest_transmission_raw`, `dump_all_rounds` and
+//!   the `Display` impls are compiled out on bpf targets rather than feature gated
+//! - the remaining helpers returning a `String` or `Vec`, e.g. [`description`],
+//!   [`feed_identity`] and [`health_many`], aren't gated at all and allocate on chain too
+//! - the `wasm` feature only adds browser bindings over the byte decoders, it doesn't pull
+//!   in `solana-client`
 
+#[cfg(feature = "client")]
+pub mod client;
+pub mod cpi;
+pub mod error;
 pub mod store;
+#[cfg(feature = "stream")]
+pub mod stream;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-use std::cell::Ref;
+use std::cmp::Ordering;
 use std::mem::size_of;
 
 use borsh::{BorshDeserialize, BorshSerialize};
-use so_defi_utils::accessor::to_u32;
-use so_defi_utils::accessor::AccessorType;
 use solana_program::{
     self, account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey,
 };
-use static_pubkey::static_pubkey;
 
-use store::with_store;
+use error::SclinkError;
+use store::{check_data_len, read_header_raw, with_store_bytes, with_store_readonly};
 
+use crate::store::FetchError;
 use crate::store::Transmission;
+use crate::store::Transmissions;
 use crate::store::HEADER_SIZE;
-pub const CHAINLINK_STORE_PROGRAM: Pubkey =
-    static_pubkey!("HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny");
+/// HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny
+pub const CHAINLINK_STORE_PROGRAM: Pubkey = Pubkey::new_from_array([
+    241, 75, 246, 90, 213, 107, 210, 186, 113, 94, 69, 116, 44, 35, 31, 39, 214, 54, 33, 207, 91,
+    119, 143, 55, 193, 162, 72, 149, 29, 23, 86, 2,
+]);
 
 pub const FEED_VERSION: u8 = 2;
 
+/// Feed layouts sclink knows the offsets of. Legacy v1 feeds use a different layout that
+/// isn't supported, they are rejected with [`SclinkError::BadVersion`] rather than misread.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+#[repr(u8)]
+pub enum FeedVersion {
+    V2 = FEED_VERSION,
+}
+
+impl TryFrom<u8> for FeedVersion {
+    type Error = ProgramError;
+
+    fn try_from(version: u8) -> Result<Self, Self::Error> {
+        match version {
+            FEED_VERSION => Ok(FeedVersion::V2),
+            found => {
+                msg!(
+                    "unsupported feed version {}, expected {}",
+                    found,
+                    FEED_VERSION
+                );
+                Err(SclinkError::BadVersion.into())
+            }
+        }
+    }
+}
+
+/// default cap on the number of round ids [`round_range`] iterates over
+pub const MAX_ROUND_RANGE: u32 = 10_000;
+
+/// decimals of the canonical WAD scale used by many DeFi math libraries
+pub const WAD_DECIMALS: u8 = 18;
+
+/// decimals most USD denominated protocols and feeds standardize on
+pub const USD_DECIMALS: u8 = 8;
+
+/// The state of a feed, [`FeedState::Unknown`] covers values introduced by later versions
+/// of the store program.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub enum FeedState {
+    Normal,
+    Flagged,
+    Unknown(u8),
+}
+
+impl From<u8> for FeedState {
+    fn from(state: u8) -> Self {
+        match state {
+            Transmissions::NORMAL => FeedState::Normal,
+            Transmissions::FLAGGED => FeedState::Flagged,
+            state => FeedState::Unknown(state),
+        }
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
 pub enum Scope {
     Version,
@@ -38,46 +125,304 @@ pub enum Scope {
     /// as LatestRoundDataWithDecimals will allocate too much memory
     /// when using withing programs
     LatestRoundDataWithDecimals2,
-    // ProposedAggregator
-    // Owner
+    Owner,
+    State,
+    Granularity,
+    FlaggingThreshold,
+    Writer,
+    RoundDataWithDecimals {
+        round_id: u32,
+    },
+    // ProposedAggregator can't be supported: the v2 transmissions layout has no proposed
+    // aggregator field. the aggregator reported by the store is the `writer`, which the
+    // owner replaces in a single step, so there is no pending migration to read. consumers
+    // that need to detect a migration should compare `aggregator()` against the expected
+    // writer instead.
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Eq)]
+/// Rounds are ordered by `round_id` first, then by slot, timestamp and answer, following
+/// the field order.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Round {
     pub round_id: u32,
     pub slot: u64,
     pub timestamp: u32,
+    /// signed, negative answers are valid for some feeds such as spreads. both the store
+    /// and borsh encode it as little endian two's complement
+    #[cfg_attr(feature = "serde", serde(with = "answer_string"))]
     pub answer: i128,
 }
 
+/// (de)serializes answers as strings, as an i128 doesn't fit a javascript number
+#[cfg(feature = "serde")]
+mod answer_string {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(answer: &i128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(answer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i128, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RoundWithDecimals {
     pub round: Round,
     pub decimals: u8,
 }
 
-pub fn query(feed: &AccountInfo, scope: Scope) -> Result<Vec<u8>, ProgramError> {
-    if feed.owner.ne(&CHAINLINK_STORE_PROGRAM) {
+impl Round {
+    pub fn with_decimals(self, decimals: u8) -> RoundWithDecimals {
+        RoundWithDecimals {
+            round: self,
+            decimals,
+        }
+    }
+}
+
+impl From<RoundWithDecimals> for Round {
+    fn from(round: RoundWithDecimals) -> Self {
+        round.round
+    }
+}
+
+impl RoundWithDecimals {
+    /// Returns the answer with the decimal point placed using integer arithmetic only,
+    /// so unlike [`RoundWithDecimals::as_f64`] the output is exact.
+    #[cfg(not(target_arch = "bpf"))]
+    pub fn format_answer(&self) -> String {
+        format_decimal(self.round.answer, self.decimals)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.round.answer.is_negative()
+    }
+
+    /// Returns the answer rescaled from the feed decimals to `target_decimals`. Scaling
+    /// down truncates towards zero, scaling up fails if the answer overflows.
+    pub fn to_scaled(&self, target_decimals: u8) -> Result<i128, ProgramError> {
+        match rescale(self.round.answer, self.decimals, target_decimals) {
+            Some(answer) => Ok(answer),
+            None => {
+                msg!("answer overflow");
+                Err(ProgramError::ArithmeticOverflow)
+            }
+        }
+    }
+
+    /// Returns the answer scaled by the decimals as a float. Answers beyond 2^53 lose
+    /// precision, so this is meant for display and logging rather than accounting.
+    pub fn as_f64(&self) -> f64 {
+        self.round.answer as f64 / 10_f64.powi(i32::from(self.decimals))
+    }
+
+    /// Returns the answer scaled by the decimals as an exact decimal. Fails if the answer
+    /// doesn't fit the 96 bit mantissa or the decimals exceed the maximum scale of 28.
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Result<rust_decimal::Decimal, rust_decimal::Error> {
+        rust_decimal::Decimal::try_from_i128_with_scale(self.round.answer, u32::from(self.decimals))
+    }
+}
+
+#[cfg(not(target_arch = "bpf"))]
+impl std::fmt::Display for Round {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "round {} @ slot {} ts {} answer {}",
+            self.round_id, self.slot, self.timestamp, self.answer
+        )
+    }
+}
+
+/// same as the [`Round`] output, with the answer scaled by the decimals
+#[cfg(not(target_arch = "bpf"))]
+impl std::fmt::Display for RoundWithDecimals {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "round {} @ slot {} ts {} answer {}",
+            self.round.round_id,
+            self.round.slot,
+            self.round.timestamp,
+            self.format_answer()
+        )
+    }
+}
+
+/// A round along with the slot and timestamp at which it was read, separating when the
+/// price was recorded from when it was consumed.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct StampedRound {
+    pub round: Round,
+    pub observed_slot: u64,
+    pub observed_timestamp: u32,
+}
+
+/// A price read, laid out as an anchor event so indexers can pick it up from the
+/// program logs. See [`emit_bytes`].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct ChainlinkPriceEvent {
+    pub feed: Pubkey,
+    pub round_id: u32,
+    pub answer: i128,
+    pub decimals: u8,
+    pub timestamp: u32,
+    pub slot: u64,
+}
+
+impl ChainlinkPriceEvent {
+    /// anchor's event discriminator, the first 8 bytes of `sha256("event:ChainlinkPriceEvent")`
+    pub const DISCRIMINATOR: [u8; 8] = [42, 21, 59, 210, 104, 68, 35, 16];
+}
+
+/// The state of a feed at a point in time, as returned by [`feed_snapshot`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct FeedSnapshot {
+    pub version: u8,
+    pub decimals: u8,
+    pub description: String,
+    pub aggregator: Pubkey,
+    pub latest: Round,
+    pub state: FeedState,
+}
+
+/// Expected configuration of a feed, e.g. from a governance approved manifest.
+///
+/// The store reports the writer as the aggregator, both are read from the same field, so
+/// `aggregator` and `writer` must be equal. A manifest where they differ never verifies.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct FeedManifest {
+    pub aggregator: Pubkey,
+    pub decimals: u8,
+    pub description: String,
+    /// must equal `aggregator`
+    pub writer: Pubkey,
+}
+
+/// The static configuration of a feed, as returned by [`feed_metadata`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct FeedMetadata {
+    pub version: u8,
+    pub decimals: u8,
+    pub description: String,
+    pub aggregator: Pubkey,
+}
+
+/// strips the null padding from the raw 32 byte description
+fn trim_description(description: &[u8]) -> &[u8] {
+    // Look for the first null byte
+    let end = description
+        .iter()
+        .position(|byte| byte == &0)
+        .unwrap_or(description.len());
+    &description[..end]
+}
+
+/// reads a pubkey out of account data, failing instead of panicking on a wrong length
+pub(crate) fn read_pubkey(bytes: &[u8]) -> Result<Pubkey, ProgramError> {
+    match Pubkey::try_from(bytes) {
+        Ok(pubkey) => Ok(pubkey),
+        Err(_) => {
+            msg!("invalid pubkey length");
+            Err(ProgramError::InvalidAccountData)
+        }
+    }
+}
+
+/// Health indicators of a feed, as returned by [`health`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct FeedHealth {
+    pub flagged: bool,
+    /// seconds elapsed since the latest round
+    pub age_secs: u32,
+    /// whether the most recent live rounds all carry the same answer
+    pub stuck: bool,
+}
+
+/// compares the description against `expected` without allocating
+fn description_eq(feed: &AccountInfo, expected: &str) -> Result<bool, ProgramError> {
+    check_owner(feed)?;
+    check_data_len(feed)?;
+    check_version(feed)?;
+    Ok(trim_description(&feed.try_borrow_data()?[106..138]) == expected.as_bytes())
+}
+
+pub(crate) fn check_owner(feed: &AccountInfo) -> Result<(), ProgramError> {
+    check_program_owner(feed, &CHAINLINK_STORE_PROGRAM)
+}
+
+fn check_program_owner(feed: &AccountInfo, expected_owner: &Pubkey) -> Result<(), ProgramError> {
+    if feed.owner.ne(expected_owner) {
         msg!("invalid program owner");
-        return Err(ProgramError::IllegalOwner);
+        return Err(SclinkError::InvalidOwner.into());
     }
+    Ok(())
+}
+
+fn check_version(feed: &AccountInfo) -> Result<FeedVersion, ProgramError> {
+    FeedVersion::try_from(read_header_raw(feed)?.version)
+}
+
+pub fn query(feed: &AccountInfo, scope: Scope) -> Result<Vec<u8>, ProgramError> {
+    query_with_program(feed, scope, &CHAINLINK_STORE_PROGRAM)
+}
+
+/// Same as [`query`] for feeds owned by a store program deployed at `expected_owner`,
+/// e.g. on devnet or a local test validator.
+pub fn query_with_program(
+    feed: &AccountInfo,
+    scope: Scope,
+    expected_owner: &Pubkey,
+) -> Result<Vec<u8>, ProgramError> {
+    check_program_owner(feed, expected_owner)?;
+    check_data_len(feed)?;
+    // the version itself sits right after the discriminator in every layout, everything
+    // else is read from v2 offsets
+    if !matches!(scope, Scope::Version) {
+        check_version(feed)?;
+    }
+    query_validated(feed, scope)
+}
+
+/// Same as [`query`] for several scopes at once, validating the feed a single time.
+pub fn query_many(feed: &AccountInfo, scopes: &[Scope]) -> Result<Vec<Vec<u8>>, ProgramError> {
+    check_owner(feed)?;
+    check_data_len(feed)?;
+    if scopes.iter().any(|scope| !matches!(scope, Scope::Version)) {
+        check_version(feed)?;
+    }
+    scopes
+        .iter()
+        .map(|scope| query_validated(feed, *scope))
+        .collect()
+}
+
+/// services a scope of a feed whose owner, length and version have already been checked
+fn query_validated(feed: &AccountInfo, scope: Scope) -> Result<Vec<u8>, ProgramError> {
     match scope {
-        Scope::Version => Ok(vec![AccessorType::U8(8).access(feed)[0]]),
-        Scope::Decimals => Ok(vec![AccessorType::U8(138).access(feed)[0]]),
+        Scope::Version => Ok(vec![read_header_raw(feed)?.version]),
+        Scope::Decimals => Ok(vec![read_header_raw(feed)?.decimals]),
         Scope::Description => {
-            // description length is 32 bytes, so we can use the Pubkey accessor
-            let description = AccessorType::Pubkey(106).access(feed);
-            // Look for the first null byte
-            let end = description
-                .iter()
-                .position(|byte| byte == &0)
-                .unwrap_or(description.len());
-            Ok(description[..end].to_vec())
+            let description = read_header_raw(feed)?.description;
+            Ok(trim_description(&description).to_vec())
         }
         Scope::RoundData { round_id } => {
-            let round = match with_store(feed, |store| store.fetch(round_id)) {
+            let round = match with_store_readonly(feed, |store| store.fetch(round_id)) {
                 Ok(store_info) => {
                     if let Some(info) = store_info {
                         info
@@ -97,91 +442,131 @@ pub fn query(feed: &AccountInfo, scope: Scope) -> Result<Vec<u8>, ProgramError>
             .try_to_vec()?)
         }
         Scope::LatestRoundData => {
-            let round = match with_store(feed, |store| store.latest()) {
+            let round = match with_store_readonly(feed, |store| store.latest()) {
                 Ok(store_info) => {
                     if let Some(info) = store_info {
                         info
                     } else {
                         msg!("failed to fetch round data");
-                        return Err(ProgramError::InvalidAccountData);
+                        return Err(SclinkError::NoRounds.into());
                     }
                 }
                 Err(err) => return Err(err),
             };
             Ok(Round {
-                round_id: to_u32(&AccessorType::U32(143).access(feed)[..]),
+                round_id: read_header_raw(feed)?.latest_round_id(),
                 slot: round.slot,
                 answer: round.answer,
                 timestamp: round.timestamp,
             }
             .try_to_vec()?)
         }
-        Scope::Aggregator => Ok(AccessorType::Pubkey(74).access(feed)),
+        Scope::Aggregator => Ok(read_header_raw(feed)?.writer.to_bytes().to_vec()),
         Scope::LatestRoundDataWithDecimals => {
-            let round = match with_store(feed, |store| store.latest()) {
+            let round = match with_store_readonly(feed, |store| store.latest()) {
                 Ok(store_info) => {
                     if let Some(info) = store_info {
                         info
                     } else {
                         msg!("failed to fetch round data");
-                        return Err(ProgramError::InvalidAccountData);
+                        return Err(SclinkError::NoRounds.into());
                     }
                 }
                 Err(err) => return Err(err),
             };
+            let header = read_header_raw(feed)?;
             Ok(RoundWithDecimals {
                 round: Round {
-                    round_id: to_u32(&AccessorType::U32(143).access(feed)[..]),
+                    round_id: header.latest_round_id(),
                     slot: round.slot,
                     answer: round.answer,
                     timestamp: round.timestamp,
                 },
-                decimals: AccessorType::U8(138).access(feed)[0],
+                decimals: header.decimals,
             }
             .try_to_vec()?)
         }
         Scope::LatestRoundDataWithDecimals2 => {
-            let response = AccessorType::U8(8).access(feed);
-            if response[0].ne(&FEED_VERSION) {
-                msg!("invalid feed version");
-                return Err(ProgramError::InvalidAccountData);
+            // every field comes from the header deserialized below
+            let data = feed.try_borrow_data()?;
+            let transmission = match Transmissions::deserialize(&mut &data[..]) {
+                Ok(transmission) => transmission,
+                Err(_) => {
+                    msg!("failed to deserialize transmissions");
+                    return Err(SclinkError::CastFailed.into());
+                }
+            };
+            if transmission.latest_round_id == 0 {
+                msg!("failed to fetch round data");
+                return Err(SclinkError::NoRounds.into());
             }
-            let n = to_u32(&AccessorType::U32(148).access(feed)[..]) as usize;
+            let n = transmission.live_length as usize;
+            // skip the discriminator and header
+            let live = match n
+                .checked_mul(size_of::<Transmission>())
+                .and_then(|live_len| data.get(8 + HEADER_SIZE..8 + HEADER_SIZE + live_len))
             {
-                let (live, _) = {
-                    let data = feed.try_borrow_data()?;
-                    std::cell::Ref::map_split(data, |data| {
-                        // skip the header
-                        let (_header, data) = data.split_at(8 + HEADER_SIZE); // discriminator + header size
-                        let (live, _) = data.split_at(n * std::mem::size_of::<Transmission>());
-                        // NOTE: no try_map_split available..
-                        let live = bytemuck::try_cast_slice::<_, Transmission>(live).unwrap();
-                        (live, live)
-                    })
-                };
-                let transmission =
-                    crate::store::Transmissions::deserialize(&mut &feed.try_borrow_data()?[..])
-                        .unwrap();
-                if transmission.latest_round_id == 0 {
-                    panic!("found is none");
+                Some(live) => live,
+                None => {
+                    msg!("account data shorter than the declared live length");
+                    return Err(ProgramError::AccountDataTooSmall);
+                }
+            };
+            // read only the latest transmission, unaligned as the account data isn't
+            // guaranteed to be aligned for Transmission off chain
+            let len = transmission.live_length;
+            let round_data = match (transmission.live_cursor + len.saturating_sub(1))
+                .checked_rem(len)
+                .and_then(|idx| {
+                    let offset = idx as usize * size_of::<Transmission>();
+                    live.get(offset..offset + size_of::<Transmission>())
+                }) {
+                Some(round_data) => bytemuck::pod_read_unaligned::<Transmission>(round_data),
+                None => {
+                    msg!("failed to fetch round data");
+                    return Err(ProgramError::InvalidAccountData);
                 }
-                let len = transmission.live_length;
-                let idx = (transmission.live_cursor + len.saturating_sub(1)) % len;
-                let (slot, answer, timestamp) = {
-                    let round_data = &live[idx as usize];
-                    (round_data.slot, round_data.answer, round_data.timestamp)
-                };
-                Ok(RoundWithDecimals {
-                    round: Round {
-                        round_id: to_u32(&AccessorType::U32(143).access(feed)[..]),
-                        slot: slot,
-                        answer: answer,
-                        timestamp: timestamp,
-                    },
-                    decimals: AccessorType::U8(138).access(feed)[0],
+            };
+            Ok(RoundWithDecimals {
+                round: Round {
+                    round_id: transmission.latest_round_id,
+                    slot: round_data.slot,
+                    answer: round_data.answer,
+                    timestamp: round_data.timestamp,
+                },
+                decimals: transmission.decimals,
+            }
+            .try_to_vec()?)
+        }
+        // discriminator, version and state precede the owner
+        Scope::Owner => Ok(read_header_raw(feed)?.owner.to_bytes().to_vec()),
+        Scope::State => Ok(vec![read_header_raw(feed)?.state]),
+        Scope::Granularity => Ok(vec![read_header_raw(feed)?.granularity]),
+        Scope::FlaggingThreshold => Ok(read_header_raw(feed)?.flagging_threshold.to_vec()),
+        // the store reports the writer as the aggregator, so this is the same field
+        Scope::Writer => Ok(read_header_raw(feed)?.writer.to_bytes().to_vec()),
+        Scope::RoundDataWithDecimals { round_id } => {
+            let round = match with_store_readonly(feed, |store| store.fetch(round_id)) {
+                Ok(store_info) => {
+                    if let Some(info) = store_info {
+                        info
+                    } else {
+                        msg!("failed to fetch round data");
+                        return Err(ProgramError::InvalidAccountData);
+                    }
                 }
-                .try_to_vec()?)
+                Err(err) => return Err(err),
+            };
+            Ok(RoundWithDecimals {
+                round: Round {
+                    round_id,
+                    slot: round.slot,
+                    answer: round.answer,
+                    timestamp: round.timestamp,
+                },
+                decimals: read_header_raw(feed)?.decimals,
             }
+            .try_to_vec()?)
         }
     }
 }
@@ -191,6 +576,12 @@ pub fn version(feed: &AccountInfo) -> Result<u8, ProgramError> {
     Ok(query(feed, Scope::Version)?[0])
 }
 
+/// Returns the layout of the feed, failing with [`SclinkError::BadVersion`] for layouts
+/// sclink can't read.
+pub fn feed_version(feed: &AccountInfo) -> Result<FeedVersion, ProgramError> {
+    FeedVersion::try_from(version(feed)?)
+}
+
 /// Returns the amount of decimal places.
 pub fn decimals(feed: &AccountInfo) -> Result<u8, ProgramError> {
     Ok(query(feed, Scope::Decimals)?[0])
@@ -207,15 +598,124 @@ pub fn description(feed: &AccountInfo) -> Result<String, ProgramError> {
     }
 }
 
+/// Same as [`description`], replacing invalid UTF-8 sequences with `U+FFFD` instead of
+/// failing.
+pub fn description_lossy(feed: &AccountInfo) -> Result<String, ProgramError> {
+    Ok(String::from_utf8_lossy(&query(feed, Scope::Description)?).into_owned())
+}
+
 /// Returns round data for the latest round.
 pub fn latest_round_data(feed: &AccountInfo) -> Result<Round, ProgramError> {
     Ok(Round::deserialize(
         &mut &query(feed, Scope::LatestRoundData)?[..],
     )?)
 }
+
+/// Same as [`latest_round_data`] for raw account data, e.g. from an archived account
+/// dump. The owner can't be checked, so the data has to come from a trusted source.
+pub fn latest_round_from_bytes(data: &[u8]) -> Result<Round, ProgramError> {
+    let latest = with_store_bytes(data, |store| {
+        let round_id = store.header.latest_round_id;
+        store.latest().map(|transmission| Round {
+            round_id,
+            slot: transmission.slot,
+            answer: transmission.answer,
+            timestamp: transmission.timestamp,
+        })
+    })?;
+    match latest {
+        Some(latest) => Ok(latest),
+        None => {
+            msg!("failed to fetch round data");
+            Err(SclinkError::NoRounds.into())
+        }
+    }
+}
+
+/// Same as [`decimals`] for raw account data.
+pub fn decimals_from_bytes(data: &[u8]) -> Result<u8, ProgramError> {
+    with_store_bytes(data, |store| store.header.decimals)
+}
+
+/// Same as [`description`] for raw account data.
+pub fn description_from_bytes(data: &[u8]) -> Result<String, ProgramError> {
+    let description = with_store_bytes(data, |store| {
+        trim_description(&store.header.description).to_vec()
+    })?;
+    match String::from_utf8(description) {
+        Ok(description) => Ok(description),
+        Err(_) => {
+            msg!("utf8 parse failed");
+            Err(ProgramError::InvalidArgument)
+        }
+    }
+}
+
+/// Returns the answer of the latest round.
+pub fn latest_answer(feed: &AccountInfo) -> Result<i128, ProgramError> {
+    Ok(latest_round_data(feed)?.answer)
+}
+
+/// Returns the unix timestamp of the latest round.
+pub fn latest_timestamp(feed: &AccountInfo) -> Result<u32, ProgramError> {
+    Ok(latest_round_data(feed)?.timestamp)
+}
+
 /// Returns the address of the underlying OCR2 aggregator.
 pub fn aggregator(feed: &AccountInfo) -> Result<Pubkey, ProgramError> {
-    Ok(Pubkey::new(&query(feed, Scope::Aggregator)?[..]))
+    read_pubkey(&query(feed, Scope::Aggregator)?)
+}
+
+/// Returns how many rounds apart the historical samples are.
+pub fn granularity(feed: &AccountInfo) -> Result<u8, ProgramError> {
+    Ok(query(feed, Scope::Granularity)?[0])
+}
+
+/// Returns the deviation threshold at which the feed gets flagged.
+pub fn flagging_threshold(feed: &AccountInfo) -> Result<u32, ProgramError> {
+    let threshold = query(feed, Scope::FlaggingThreshold)?;
+    Ok(u32::from_le_bytes([
+        threshold[0],
+        threshold[1],
+        threshold[2],
+        threshold[3],
+    ]))
+}
+
+/// Returns the state of the feed.
+pub fn feed_state(feed: &AccountInfo) -> Result<FeedState, ProgramError> {
+    Ok(FeedState::from(query(feed, Scope::State)?[0]))
+}
+
+/// Returns true if the feed has been flagged.
+pub fn is_flagged(feed: &AccountInfo) -> Result<bool, ProgramError> {
+    Ok(feed_state(feed)? == FeedState::Flagged)
+}
+
+/// Returns true once the feed has received its first round, without going through the
+/// error path of the round data accessors.
+pub fn is_initialized(feed: &AccountInfo) -> Result<bool, ProgramError> {
+    check_owner(feed)?;
+    check_data_len(feed)?;
+    check_version(feed)?;
+    Ok(read_header_raw(feed)?.latest_round_id() != 0)
+}
+
+/// Returns the owner of the feed.
+pub fn owner(feed: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    read_pubkey(&query(feed, Scope::Owner)?)
+}
+
+/// Returns the access controller of the store account, which gates lowering the
+/// flagging threshold. Unlike the other accessors this reads the store account, not a
+/// feed.
+pub fn lowering_access_controller(store: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    store::read_lowering_access_controller(store)
+}
+
+/// Returns the OCR2 transmitter allowed to write to the feed.
+pub fn writer(feed: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    read_pubkey(&query(feed, Scope::Writer)?)
 }
 
 /// Returns round data for the latest round, including decimal value
@@ -227,7 +727,18 @@ pub fn latest_round_data_with_decimals(
     )?)
 }
 
-/// same as latest_round_data_with_decimals2 but attempts to reduce the number of allocations
+/// Returns round data for the given round, including decimal value
+pub fn round_data_with_decimals(
+    feed: &AccountInfo,
+    round_id: u32,
+) -> Result<RoundWithDecimals, ProgramError> {
+    Ok(RoundWithDecimals::deserialize(
+        &mut &query(feed, Scope::RoundDataWithDecimals { round_id })?[..],
+    )?)
+}
+
+/// same as latest_round_data_with_decimals but attempts to reduce the number of allocations,
+/// see `tests/allocations.rs`
 pub fn latest_round_data_with_decimals2(
     feed: &AccountInfo,
 ) -> Result<RoundWithDecimals, ProgramError> {
@@ -236,12 +747,2320 @@ pub fn latest_round_data_with_decimals2(
     )?)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use solana_program::account_info::IntoAccountInfo;
-    use static_pubkey::static_pubkey;
+/// Returns the version, decimals, description, aggregator, latest round and state of a
+/// feed, validated once and decoded from a single borrow of the account data.
+pub fn feed_snapshot(feed: &AccountInfo) -> Result<FeedSnapshot, ProgramError> {
+    check_owner(feed)?;
+    check_data_len(feed)?;
+    check_version(feed)?;
+    let data = feed.try_borrow_data()?;
+    let description = match String::from_utf8(trim_description(&data[106..138]).to_vec()) {
+        Ok(description) => description,
+        Err(_) => {
+            msg!("utf8 parse failed");
+            return Err(ProgramError::InvalidArgument);
+        }
+    };
+    let latest = with_store_bytes(&data, |store| {
+        store.latest().map(|transmission| Round {
+            round_id: store.header.latest_round_id,
+            slot: transmission.slot,
+            answer: transmission.answer,
+            timestamp: transmission.timestamp,
+        })
+    })?;
+    let latest = match latest {
+        Some(latest) => latest,
+        None => {
+            msg!("failed to fetch round data");
+            return Err(SclinkError::NoRounds.into());
+        }
+    };
+    Ok(FeedSnapshot {
+        version: data[8],
+        decimals: data[138],
+        description,
+        aggregator: read_pubkey(&data[74..106])?,
+        latest,
+        state: FeedState::from(data[9]),
+    })
+}
+
+/// Returns the version, decimals, description and aggregator of a feed, decoded from a
+/// single borrow of the account data.
+pub fn feed_metadata(feed: &AccountInfo) -> Result<FeedMetadata, ProgramError> {
+    check_owner(feed)?;
+    check_data_len(feed)?;
+    check_version(feed)?;
+    let data = feed.try_borrow_data()?;
+    let description = match String::from_utf8(trim_description(&data[106..138]).to_vec()) {
+        Ok(description) => description,
+        Err(_) => {
+            msg!("utf8 parse failed");
+            return Err(ProgramError::InvalidArgument);
+        }
+    };
+    Ok(FeedMetadata {
+        version: data[8],
+        decimals: data[138],
+        description,
+        aggregator: read_pubkey(&data[74..106])?,
+    })
+}
+
+/// Same as [`feed_snapshot`], but a description that isn't valid utf8 is replaced by an
+/// empty string instead of failing the whole snapshot. Every other field, including the
+/// latest round, must decode successfully.
+pub fn snapshot_lenient(feed: &AccountInfo) -> Result<FeedSnapshot, ProgramError> {
+    let description = match String::from_utf8(query(feed, Scope::Description)?) {
+        Ok(description) => description,
+        Err(_) => {
+            msg!("utf8 parse failed, using an empty description");
+            String::new()
+        }
+    };
+    Ok(FeedSnapshot {
+        version: version(feed)?,
+        decimals: decimals(feed)?,
+        description,
+        aggregator: aggregator(feed)?,
+        latest: latest_round_data(feed)?,
+        state: feed_state(feed)?,
+    })
+}
+
+/// Returns the snapshot of a feed along with a copy of the account data it was decoded
+/// from, so the snapshot can be independently verified. Note that this copies the whole
+/// account, which is several megabytes for feeds with a day of live data.
+pub fn snapshot_with_raw(feed: &AccountInfo) -> Result<(FeedSnapshot, Vec<u8>), ProgramError> {
+    let snapshot = feed_snapshot(feed)?;
+    let raw = feed.try_borrow_data()?.to_vec();
+    Ok((snapshot, raw))
+}
+
+/// Returns an identifier for the asset pair of a feed, independent of its address, built
+/// from the lowercased description and the decimals, e.g. `btc-usd-8` for "BTC / USD".
+/// Any run of characters other than ascii letters and digits becomes a single `-`.
+pub fn feed_identity(feed: &AccountInfo) -> Result<String, ProgramError> {
+    let description = description(feed)?;
+    let mut identity = String::with_capacity(description.len() + 4);
+    for c in description.chars() {
+        if c.is_ascii_alphanumeric() {
+            identity.push(c.to_ascii_lowercase());
+        } else if !identity.is_empty() && !identity.ends_with('-') {
+            identity.push('-');
+        }
+    }
+    if !identity.is_empty() && !identity.ends_with('-') {
+        identity.push('-');
+    }
+    identity.push_str(&decimals(feed)?.to_string());
+    Ok(identity)
+}
+
+/// Checks that the feed matches every field of `manifest`, logging the first field that
+/// doesn't match.
+pub fn verify_against_manifest(
+    feed: &AccountInfo,
+    manifest: &FeedManifest,
+) -> Result<(), ProgramError> {
+    let mismatch = if aggregator(feed)?.ne(&manifest.aggregator) {
+        Some("aggregator")
+    } else if decimals(feed)?.ne(&manifest.decimals) {
+        Some("decimals")
+    } else if !description_eq(feed, &manifest.description)? {
+        Some("description")
+    } else if writer(feed)?.ne(&manifest.writer) {
+        Some("writer")
+    } else {
+        None
+    };
+    match mismatch {
+        Some(field) => {
+            msg!("manifest mismatch: {}", field);
+            Err(ProgramError::InvalidAccountData)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Ensures the feed is the one expected by checking its aggregator and description, so
+/// another store owned feed can't be substituted for it.
+pub fn verify_feed(
+    feed: &AccountInfo,
+    expected_aggregator: &Pubkey,
+    expected_description: &str,
+) -> Result<(), ProgramError> {
+    let mismatch = if aggregator(feed)?.ne(expected_aggregator) {
+        Some("aggregator")
+    } else if !description_eq(feed, expected_description)? {
+        Some("description")
+    } else {
+        None
+    };
+    match mismatch {
+        Some(field) => {
+            msg!("feed mismatch: {}", field);
+            Err(ProgramError::InvalidAccountData)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns the health of a feed at `now_ts`. The feed is considered stuck when its last
+/// `stuck_repeats` live rounds all carry the same answer, which requires at least two
+/// rounds to compare.
+pub fn health(
+    feed: &AccountInfo,
+    now_ts: u32,
+    stuck_repeats: u32,
+) -> Result<FeedHealth, ProgramError> {
+    let flagged = is_flagged(feed)?;
+    let latest = latest_round_data(feed)?;
+    let stuck = stuck_repeats >= 2
+        && with_store_readonly(feed, |store| {
+            let mut compared = 0;
+            let repeated = store.live_iter().take(stuck_repeats as usize).all(|round| {
+                compared += 1;
+                round.answer == latest.answer
+            });
+            repeated && compared == stuck_repeats
+        })?;
+    Ok(FeedHealth {
+        flagged,
+        age_secs: now_ts.saturating_sub(latest.timestamp),
+        stuck,
+    })
+}
+
+/// Returns the health of each feed, see [`health`]. Every feed is checked independently
+/// so a single invalid feed doesn't abort the sweep.
+pub fn health_many(
+    feeds: &[&AccountInfo],
+    now_ts: u32,
+    stuck_repeats: u32,
+) -> Vec<Result<FeedHealth, ProgramError>> {
+    feeds
+        .iter()
+        .map(|feed| health(feed, now_ts, stuck_repeats))
+        .collect()
+}
+
+/// Returns true if the last `lookback` live rounds all carry the same answer while their
+/// timestamps keep strictly increasing, i.e. the writer is still transmitting but the
+/// answer doesn't move. A timestamp based staleness check can't catch this. Requires at
+/// least two rounds to compare, fewer rounds than `lookback` are never stalled.
+pub fn is_stalled(feed: &AccountInfo, lookback: u32) -> Result<bool, ProgramError> {
+    check_owner(feed)?;
+    if lookback < 2 {
+        return Ok(false);
+    }
+    with_store_readonly(feed, |store| {
+        let mut rounds = store.live_iter().take(lookback as usize);
+        let mut newer = match rounds.next() {
+            Some(latest) => latest,
+            None => return false,
+        };
+        let mut compared = 1;
+        for older in rounds {
+            // newest first, so timestamps have to be decreasing
+            if older.answer != newer.answer || older.timestamp >= newer.timestamp {
+                return false;
+            }
+            newer = older;
+            compared += 1;
+        }
+        compared == lookback
+    })
+}
+
+/// Returns the byte offset of the latest live transmission along with the latest round
+/// id, read straight from the header fields so the buffers don't need to be deserialized.
+fn latest_live_offset(data: &[u8]) -> Result<(usize, u32), ProgramError> {
+    let header = store::header_ref(data)?;
+    FeedVersion::try_from(header.version)?;
+    let latest_round_id = header.latest_round_id();
+    let live_length = header.live_length();
+    if latest_round_id == 0 || live_length == 0 {
+        msg!("failed to fetch round data");
+        return Err(SclinkError::NoRounds.into());
+    }
+    // Handle wraparound
+    let index =
+        (u64::from(header.live_cursor()) + u64::from(live_length) - 1) % u64::from(live_length);
+    let offset = 8 + HEADER_SIZE + index as usize * size_of::<Transmission>();
+    if offset + size_of::<Transmission>() > data.len() {
+        msg!("account data shorter than the declared live length");
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    Ok((offset, latest_round_id))
+}
+
+/// Returns round data for the latest round, reading only the header fields needed to
+/// locate it and the latest transmission itself from a single borrow. Unlike
+/// `latest_round_data` the header isn't deserialized and the ring buffers aren't touched.
+pub fn latest_round_fast(feed: &AccountInfo) -> Result<Round, ProgramError> {
+    check_owner(feed)?;
+    let data = feed.try_borrow_data()?;
+    let (offset, round_id) = latest_live_offset(&data)?;
+    let transmission: Transmission =
+        bytemuck::pod_read_unaligned(&data[offset..offset + size_of::<Transmission>()]);
+    Ok(Round {
+        round_id,
+        slot: transmission.slot,
+        answer: transmission.answer,
+        timestamp: transmission.timestamp,
+    })
+}
+
+/// Returns the raw bytes of the latest transmission including its padding, for forensic
+/// inspection of what the writer actually stored.
+#[cfg(not(target_arch = "bpf"))]
+pub fn latest_transmission_raw(feed: &AccountInfo) -> Result<[u8; 48], ProgramError> {
+    check_owner(feed)?;
+    let data = feed.try_borrow_data()?;
+    let (offset, _) = latest_live_offset(&data)?;
+    let mut raw = [0; 48];
+    raw.copy_from_slice(&data[offset..offset + 48]);
+    Ok(raw)
+}
+
+/// Returns the latest round of each feed, in order. Stops at the first feed that fails,
+/// logging its index.
+pub fn latest_round_data_batch(feeds: &[&AccountInfo]) -> Result<Vec<Round>, ProgramError> {
+    feeds
+        .iter()
+        .enumerate()
+        .map(|(index, feed)| match latest_round_data(feed) {
+            Ok(round) => Ok(round),
+            Err(err) => {
+                msg!("failed to read feed {}", index);
+                Err(err)
+            }
+        })
+        .collect()
+}
+
+/// Returns true if the decimals or description of the feed differ from the cached values.
+pub fn config_drifted(
+    feed: &AccountInfo,
+    cached_decimals: u8,
+    cached_description: &str,
+) -> Result<bool, ProgramError> {
+    Ok(decimals(feed)? != cached_decimals || !description_eq(feed, cached_description)?)
+}
+
+/// Returns the latest round, rejecting it if it is more than `max_age_secs` older than
+/// `now_unix`, typically `Clock::get()?.unix_timestamp`. Rounds stamped in the future
+/// are treated as fresh.
+pub fn latest_round_checked(
+    feed: &AccountInfo,
+    now_unix: u32,
+    max_age_secs: u32,
+) -> Result<Round, ProgramError> {
+    let latest = latest_round_data(feed)?;
+    if now_unix.saturating_sub(latest.timestamp) > max_age_secs {
+        msg!("stale price");
+        return Err(SclinkError::StalePrice.into());
+    }
+    Ok(latest)
+}
+
+/// Returns the latest round stamped with the caller supplied slot and unix timestamp,
+/// typically taken from the `Clock` sysvar.
+pub fn latest_round_stamped(
+    feed: &AccountInfo,
+    current_slot: u64,
+    current_ts: u32,
+) -> Result<StampedRound, ProgramError> {
+    Ok(StampedRound {
+        round: latest_round_data(feed)?,
+        observed_slot: current_slot,
+        observed_timestamp: current_ts,
+    })
+}
+
+/// Returns the latest round of the feed as a discriminated [`ChainlinkPriceEvent`],
+/// ready to be passed to `sol_log_data` the same way anchor's `emit!` does.
+pub fn emit_bytes(feed: &AccountInfo) -> Result<Vec<u8>, ProgramError> {
+    let latest = latest_round_data_with_decimals(feed)?;
+    let event = ChainlinkPriceEvent {
+        feed: *feed.key,
+        round_id: latest.round.round_id,
+        answer: latest.round.answer,
+        decimals: latest.decimals,
+        timestamp: latest.round.timestamp,
+        slot: latest.round.slot,
+    };
+    let mut bytes = ChainlinkPriceEvent::DISCRIMINATOR.to_vec();
+    event.serialize(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Returns the latest answer clamped into `[min, max]`, and whether clamping occurred.
+/// Use with care: clamping hides bad data from the caller, check the returned flag
+/// rather than silently trusting the clamped value.
+pub fn latest_answer_clamped(
+    feed: &AccountInfo,
+    min: i128,
+    max: i128,
+) -> Result<(i128, bool), ProgramError> {
+    if min > max {
+        msg!("invalid clamp range");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let answer = latest_round_data(feed)?.answer;
+    let clamped = answer.clamp(min, max);
+    Ok((clamped, clamped != answer))
+}
+
+/// Returns the seconds elapsed between the previous and the latest live round, or None
+/// if the live buffer holds fewer than two rounds.
+pub fn latest_interval_secs(feed: &AccountInfo) -> Result<Option<u32>, ProgramError> {
+    check_owner(feed)?;
+    with_store_readonly(feed, |store| {
+        let mut rounds = store.live_iter();
+        let latest = rounds.next()?;
+        let previous = rounds.next()?;
+        Some(latest.timestamp.saturating_sub(previous.timestamp))
+    })
+}
+
+/// Returns the retained rounds with ids in `[start_round, end_round]`, oldest first.
+/// Rounds that are only available rounded down to a historical sample are skipped, so
+/// every returned round carries its own data. Ranges spanning more than
+/// [`MAX_ROUND_RANGE`] rounds are rejected.
+pub fn round_range(
+    feed: &AccountInfo,
+    start_round: u32,
+    end_round: u32,
+) -> Result<Vec<Round>, ProgramError> {
+    round_range_capped(feed, start_round, end_round, MAX_ROUND_RANGE)
+}
+
+/// Same as [`round_range`] with a custom cap on the number of round ids in the range.
+pub fn round_range_capped(
+    feed: &AccountInfo,
+    start_round: u32,
+    end_round: u32,
+    max_rounds: u32,
+) -> Result<Vec<Round>, ProgramError> {
+    if end_round.saturating_sub(start_round) >= max_rounds {
+        msg!("round range too large");
+        return Err(ProgramError::InvalidArgument);
+    }
+    check_owner(feed)?;
+    with_store_readonly(feed, |store| {
+        (start_round..=end_round)
+            .filter_map(|round_id| {
+                let transmission = store.fetch_exact(round_id).ok()?;
+                Some(Round {
+                    round_id,
+                    slot: transmission.slot,
+                    answer: transmission.answer,
+                    timestamp: transmission.timestamp,
+                })
+            })
+            .collect()
+    })
+}
+
+/// Returns the oldest round still in the live buffer, to tell whether older rounds have
+/// to be read from the historical samples.
+pub fn oldest_round_data(feed: &AccountInfo) -> Result<Round, ProgramError> {
+    check_owner(feed)?;
+    let oldest = with_store_readonly(feed, |store| {
+        let latest_round_id = store.header.latest_round_id;
+        let populated = store.header.live_length.min(latest_round_id);
+        store.oldest().map(|transmission| Round {
+            round_id: latest_round_id - populated + 1,
+            slot: transmission.slot,
+            answer: transmission.answer,
+            timestamp: transmission.timestamp,
+        })
+    })?;
+    match oldest {
+        Some(oldest) => Ok(oldest),
+        None => {
+            msg!("failed to fetch round data");
+            Err(SclinkError::NoRounds.into())
+        }
+    }
+}
+
+/// Returns the timestamps of the oldest and latest rounds in the live buffer, as
+/// `(oldest_ts, newest_ts)`, or None if the feed has no rounds. Rounds older than
+/// `oldest_ts` are only available from the historical samples.
+pub fn live_time_span(feed: &AccountInfo) -> Result<Option<(u32, u32)>, ProgramError> {
+    check_owner(feed)?;
+    with_store_readonly(feed, |store| {
+        Some((store.oldest()?.timestamp, store.latest()?.timestamp))
+    })
+}
+
+/// Returns the timestamp of the given round, or None if it is no longer retained. Rounds
+/// that left the live buffer resolve to the historical sample they round down to.
+pub fn round_timestamp(feed: &AccountInfo, round_id: u32) -> Result<Option<u32>, ProgramError> {
+    check_owner(feed)?;
+    with_store_readonly(feed, |store| {
+        store.fetch(round_id).map(|round| round.timestamp)
+    })
+}
+
+/// Returns the round `back` rounds before the latest one, so `0` is the latest round and
+/// `1` the one before it. Rounds that left the live buffer resolve to the historical
+/// sample they round down to, reported under that sample's round id. Returns None once
+/// `back` reaches past the retained rounds.
+pub fn nth_latest_round(feed: &AccountInfo, back: u32) -> Result<Option<Round>, ProgramError> {
+    check_owner(feed)?;
+    with_store_readonly(feed, |store| {
+        let round_id = match store.header.latest_round_id.checked_sub(back) {
+            Some(round_id) if round_id > 0 => round_id,
+            _ => return None,
+        };
+        let transmission = store.fetch(round_id)?;
+        let round_id = match store.fetch_exact(round_id) {
+            Err(FetchError::RoundedToHistorical { returned_round_id }) => returned_round_id,
+            _ => round_id,
+        };
+        Some(Round {
+            round_id,
+            slot: transmission.slot,
+            answer: transmission.answer,
+            timestamp: transmission.timestamp,
+        })
+    })
+}
+
+/// Returns how many rounds the live buffer holds out of its capacity, as
+/// `(populated_rounds, live_length)`. The buffer is still ramping up until both match.
+pub fn live_fill(feed: &AccountInfo) -> Result<(u32, u32), ProgramError> {
+    check_owner(feed)?;
+    check_data_len(feed)?;
+    check_version(feed)?;
+    let header = read_header_raw(feed)?;
+    let live_length = header.live_length();
+    Ok((header.latest_round_id().min(live_length), live_length))
+}
+
+/// Returns the capacities of the live and historical buffers, as
+/// `(live_length, historical_length)`.
+pub fn buffer_lengths(feed: &AccountInfo) -> Result<(u32, u32), ProgramError> {
+    check_owner(feed)?;
+    with_store_readonly(feed, |store| {
+        (store.header.live_length, store.historical_length() as u32)
+    })
+}
+
+/// Returns the raw ring buffer cursors, as `(live_cursor, historical_cursor)`. Each one
+/// points one past the latest write into its buffer, wrapping around to 0.
+pub fn cursors(feed: &AccountInfo) -> Result<(u32, u32), ProgramError> {
+    check_owner(feed)?;
+    check_data_len(feed)?;
+    check_version(feed)?;
+    let header = read_header_raw(feed)?;
+    Ok((header.live_cursor(), header.historical_cursor()))
+}
+
+/// Returns up to `n` of the most recent live rounds, newest first.
+pub fn recent_rounds(feed: &AccountInfo, n: usize) -> Result<Vec<Round>, ProgramError> {
+    check_owner(feed)?;
+    with_store_readonly(feed, |store| {
+        let latest_round_id = store.header.latest_round_id;
+        store
+            .live_iter()
+            .take(n)
+            .zip(0..)
+            .map(|(transmission, back)| Round {
+                round_id: latest_round_id - back,
+                slot: transmission.slot,
+                answer: transmission.answer,
+                timestamp: transmission.timestamp,
+            })
+            .collect()
+    })
+}
+
+/// Returns the time weighted average answer over the `window_secs` preceding the latest
+/// round. Each live round is weighted by how long it stood before being replaced, so the
+/// latest answer itself carries no weight unless it is the only sample. If the live
+/// buffer covers less than the window, the available rounds are used.
+pub fn twap(feed: &AccountInfo, window_secs: u32) -> Result<i128, ProgramError> {
+    check_owner(feed)?;
+    with_store_readonly(feed, |store| {
+        let mut rounds = store.live_iter();
+        let latest = match rounds.next() {
+            Some(latest) => latest,
+            None => {
+                msg!("failed to fetch round data");
+                return Err(SclinkError::NoRounds.into());
+            }
+        };
+        let mut newer = latest;
+        let mut weighted: i128 = 0;
+        let mut covered: u32 = 0;
+        for older in rounds {
+            if covered >= window_secs {
+                break;
+            }
+            // zero width intervals from equal timestamps contribute nothing
+            let dt = newer
+                .timestamp
+                .saturating_sub(older.timestamp)
+                .min(window_secs - covered);
+            weighted = match older
+                .answer
+                .checked_mul(i128::from(dt))
+                .and_then(|weight| weighted.checked_add(weight))
+            {
+                Some(weighted) => weighted,
+                None => {
+                    msg!("answer overflow");
+                    return Err(ProgramError::ArithmeticOverflow);
+                }
+            };
+            covered += dt;
+            newer = older;
+        }
+        if covered == 0 {
+            return Ok(latest.answer);
+        }
+        Ok(weighted / i128::from(covered))
+    })?
+}
+
+/// fetches the answers of two rounds in a single pass over the store
+fn answers_of(
+    feed: &AccountInfo,
+    from_round: u32,
+    to_round: u32,
+) -> Result<(i128, i128), ProgramError> {
+    check_owner(feed)?;
+    let answers = with_store_readonly(feed, |store| {
+        Some((
+            store.fetch(from_round)?.answer,
+            store.fetch(to_round)?.answer,
+        ))
+    })?;
+    match answers {
+        Some(answers) => Ok(answers),
+        None => {
+            msg!("failed to fetch round data");
+            Err(ProgramError::InvalidAccountData)
+        }
+    }
+}
+
+/// Returns the change in answer from `from_round` to `to_round`.
+pub fn price_change(
+    feed: &AccountInfo,
+    from_round: u32,
+    to_round: u32,
+) -> Result<i128, ProgramError> {
+    let (from, to) = answers_of(feed, from_round, to_round)?;
+    match to.checked_sub(from) {
+        Some(change) => Ok(change),
+        None => {
+            msg!("answer overflow");
+            Err(ProgramError::ArithmeticOverflow)
+        }
+    }
+}
+
+/// Returns the change in answer from `from_round` to `to_round` in basis points of the
+/// `from` answer, truncated towards zero. A zero `from` answer is rejected.
+pub fn price_change_bps(
+    feed: &AccountInfo,
+    from_round: u32,
+    to_round: u32,
+) -> Result<i128, ProgramError> {
+    let (from, to) = answers_of(feed, from_round, to_round)?;
+    if from == 0 {
+        msg!("zero answer");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    match to
+        .checked_sub(from)
+        .and_then(|change| change.checked_mul(10_000))
+        .and_then(|change| change.checked_div(from))
+    {
+        Some(bps) => Ok(bps),
+        None => {
+            msg!("answer overflow");
+            Err(ProgramError::ArithmeticOverflow)
+        }
+    }
+}
+
+/// Returns the median of the latest `n` live answers, clamped to the populated rounds.
+/// With an even number of answers the two middle ones are averaged, rounding down.
+pub fn median_recent(feed: &AccountInfo, n: usize) -> Result<i128, ProgramError> {
+    check_owner(feed)?;
+    let mut answers = with_store_readonly(feed, |store| {
+        store
+            .live_iter()
+            .take(n)
+            .map(|round| round.answer)
+            .collect::<Vec<_>>()
+    })?;
+    if answers.is_empty() {
+        msg!("failed to fetch round data");
+        return Err(SclinkError::NoRounds.into());
+    }
+    answers.sort_unstable();
+    let middle = answers.len() / 2;
+    if answers.len() % 2 == 1 {
+        return Ok(answers[middle]);
+    }
+    let (low, high) = (answers[middle - 1], answers[middle]);
+    // floor of the average without overflowing
+    Ok((low & high) + ((low ^ high) >> 1))
+}
+
+/// Returns the most recent retained round with a timestamp at or before `ts`, for
+/// valuing positions as of a given block time. Only the live rounds and the historical
+/// samples are searched, so older timestamps resolve to a coarser round or None.
+pub fn round_at_timestamp(feed: &AccountInfo, ts: u32) -> Result<Option<Round>, ProgramError> {
+    check_owner(feed)?;
+    with_store_readonly(feed, |store| {
+        store
+            .indexed_round_at_or_before(ts)
+            .map(|(round_id, transmission)| Round {
+                round_id,
+                slot: transmission.slot,
+                answer: transmission.answer,
+                timestamp: transmission.timestamp,
+            })
+    })
+}
+
+/// Linearly interpolates the answer at `ts` between the two live rounds around it, for
+/// drawing a continuous price curve rather than steps. Returns None if `ts` is outside
+/// of the live buffer's time span. Interpolated answers truncate towards zero.
+pub fn interpolated_answer(feed: &AccountInfo, ts: u32) -> Result<Option<i128>, ProgramError> {
+    check_owner(feed)?;
+    let interpolated = with_store_readonly(feed, |store| {
+        let mut newer = store.live_iter().peekable();
+        let latest = newer.peek()?;
+        if latest.timestamp == ts {
+            return Some(Some(latest.answer));
+        }
+        let (older, newer) = store
+            .live_iter()
+            .skip(1)
+            .zip(newer)
+            .find(|(older, newer)| older.timestamp <= ts && ts <= newer.timestamp)?;
+        if older.timestamp == newer.timestamp {
+            return Some(Some(newer.answer));
+        }
+        let elapsed = i128::from(ts - older.timestamp);
+        let span = i128::from(newer.timestamp - older.timestamp);
+        Some(
+            newer
+                .answer
+                .checked_sub(older.answer)
+                .and_then(|delta| delta.checked_mul(elapsed))
+                .and_then(|delta| older.answer.checked_add(delta / span)),
+        )
+    })?;
+    match interpolated {
+        None => Ok(None),
+        Some(Some(answer)) => Ok(Some(answer)),
+        Some(None) => {
+            msg!("answer overflow");
+            Err(ProgramError::ArithmeticOverflow)
+        }
+    }
+}
+
+/// Returns the latest answer rescaled from the feed decimals to `token_decimals`,
+/// for converting a price into the smallest unit of a token. Scaling down truncates.
+/// Non-positive answers and overflows are rejected.
+pub fn answer_in_units(feed: &AccountInfo, token_decimals: u8) -> Result<u128, ProgramError> {
+    let latest = latest_round_data_with_decimals(feed)?;
+    if latest.round.answer <= 0 {
+        msg!("non-positive answer");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    match rescale(latest.round.answer, latest.decimals, token_decimals) {
+        Some(answer) => Ok(answer as u128),
+        None => {
+            msg!("answer overflow");
+            Err(ProgramError::ArithmeticOverflow)
+        }
+    }
+}
+
+/// Returns the latest answer rescaled to 18 decimals (WAD), so prices from feeds with
+/// different decimals can be used interchangeably. Feeds with more than 18 decimals are
+/// truncated, and an answer that doesn't fit an i128 once scaled up is rejected.
+pub fn latest_answer_wad(feed: &AccountInfo) -> Result<i128, ProgramError> {
+    latest_round_data_with_decimals(feed)?.to_scaled(WAD_DECIMALS)
+}
+
+/// Returns the latest answer scaled to [`USD_DECIMALS`]. Feeds with more decimals are
+/// truncated, and an answer that doesn't fit an i128 once scaled up is rejected.
+pub fn latest_answer_usd(feed: &AccountInfo) -> Result<i128, ProgramError> {
+    latest_round_data_with_decimals(feed)?.to_scaled(USD_DECIMALS)
+}
+
+/// Returns the latest answer as a `(numerator, denominator)` price ratio, where the
+/// denominator is `10^decimals`, for cross-multiplication without precision loss.
+/// Non-positive answers and decimals too large for the denominator are rejected.
+pub fn latest_price_ratio(feed: &AccountInfo) -> Result<(u128, u128), ProgramError> {
+    let latest = latest_round_data_with_decimals(feed)?;
+    if latest.round.answer <= 0 {
+        msg!("non-positive answer");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    match 10_u128.checked_pow(u32::from(latest.decimals)) {
+        Some(denominator) => Ok((latest.round.answer as u128, denominator)),
+        None => {
+            msg!("decimals overflow");
+            Err(ProgramError::ArithmeticOverflow)
+        }
+    }
+}
+
+/// Returns true if the latest answer deviates from an external `reference` price by at
+/// most `max_bps` basis points of the reference, after bringing both to the larger of
+/// the two decimals. A zero reference is rejected.
+pub fn within_deviation(
+    feed: &AccountInfo,
+    reference: i128,
+    reference_decimals: u8,
+    max_bps: u32,
+) -> Result<bool, ProgramError> {
+    if reference == 0 {
+        msg!("zero reference price");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let latest = latest_round_data_with_decimals(feed)?;
+    let decimals = latest.decimals.max(reference_decimals);
+    let deviation = rescale(latest.round.answer, latest.decimals, decimals)
+        .zip(rescale(reference, reference_decimals, decimals))
+        .and_then(|(answer, reference)| {
+            answer
+                .checked_sub(reference)?
+                .checked_abs()?
+                .checked_mul(10_000)?
+                .checked_div(reference.checked_abs()?)
+        });
+    match deviation {
+        Some(bps) => Ok(bps <= i128::from(max_bps)),
+        None => {
+            msg!("answer overflow");
+            Err(ProgramError::ArithmeticOverflow)
+        }
+    }
+}
+
+/// Compares the latest answers of two feeds after scaling both to the larger of their
+/// decimals, so feeds with different decimals compare by value rather than raw answer.
+pub fn compare_feeds(a: &AccountInfo, b: &AccountInfo) -> Result<Ordering, ProgramError> {
+    let a = latest_round_data_with_decimals(a)?;
+    let b = latest_round_data_with_decimals(b)?;
+    let decimals = a.decimals.max(b.decimals);
+    match rescale(a.round.answer, a.decimals, decimals).zip(rescale(
+        b.round.answer,
+        b.decimals,
+        decimals,
+    )) {
+        Some((a, b)) => Ok(a.cmp(&b)),
+        None => {
+            msg!("answer overflow");
+            Err(ProgramError::ArithmeticOverflow)
+        }
+    }
+}
+
+/// moves `answer` from `from` decimals to `to` decimals, returning None on overflow.
+/// scaling down truncates towards zero
+fn rescale(answer: i128, from: u8, to: u8) -> Option<i128> {
+    if to >= from {
+        answer.checked_mul(10_i128.checked_pow(u32::from(to - from))?)
+    } else {
+        // anything past 10^38 truncates every i128 to zero
+        Some(
+            10_i128
+                .checked_pow(u32::from(from - to))
+                .map_or(0, |divisor| answer / divisor),
+        )
+    }
+}
+
+/// Returns every retained round, oldest first, e.g. for backfilling a time series
+/// database from a single account fetch. Historical samples are only included for rounds
+/// that have already left the live buffer, so every round id appears once.
+#[cfg(not(target_arch = "bpf"))]
+pub fn dump_all_rounds(feed: &AccountInfo) -> Result<Vec<Round>, ProgramError> {
+    check_owner(feed)?;
+    with_store_readonly(feed, |store| {
+        store
+            .series()
+            .into_iter()
+            .map(|(round_id, transmission)| Round {
+                round_id,
+                slot: transmission.slot,
+                answer: transmission.answer,
+                timestamp: transmission.timestamp,
+            })
+            .collect()
+    })
+}
+
+/// Returns every retained round as csv, including the answer scaled by the feed decimals.
+#[cfg(feature = "client")]
+pub fn to_csv(feed: &AccountInfo) -> Result<String, ProgramError> {
+    let decimals = decimals(feed)?;
+    let mut csv = String::from("round_id,slot,timestamp,answer,scaled_answer\n");
+    for round in dump_all_rounds(feed)? {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            round.round_id,
+            round.slot,
+            round.timestamp,
+            round.answer,
+            format_decimal(round.answer, decimals)
+        ));
+    }
+    Ok(csv)
+}
+
+/// places the decimal point `decimals` digits from the right using integer arithmetic only
+#[cfg(any(feature = "client", not(target_arch = "bpf")))]
+fn format_decimal(answer: i128, decimals: u8) -> String {
+    let sign = if answer < 0 { "-" } else { "" };
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return format!("{}{}", sign, answer.unsigned_abs());
+    }
+    let digits = format!("{:0>width$}", answer.unsigned_abs(), width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::store::Store;
+    use crate::test_utils::FeedBuilder;
+    #[cfg(feature = "client")]
+    use solana_program::account_info::IntoAccountInfo;
+    #[cfg(feature = "client")]
+    use static_pubkey::static_pubkey;
+
+    /// owns the data of a synthetic feed account. the backing buffer is offset by 8 bytes
+    /// so that the transmissions following the header are 16 byte aligned for bytemuck
+    struct TestFeed {
+        key: Pubkey,
+        lamports: u64,
+        buffer: Vec<u128>,
+        len: usize,
+    }
+
+    impl TestFeed {
+        /// builds a feed the same way the store program would, by inserting `rounds` in order
+        fn new(
+            decimals: u8,
+            granularity: u8,
+            live_length: usize,
+            historical_length: usize,
+            rounds: &[Transmission],
+        ) -> TestFeed {
+            let builder = rounds.iter().fold(
+                FeedBuilder::new()
+                    .decimals(decimals)
+                    .granularity(granularity)
+                    .live_length(live_length)
+                    .historical_length(historical_length),
+                |builder, round| builder.insert_round(*round),
+            );
+            TestFeed::from_data(&builder.build())
+        }
+        fn zeroed(len: usize) -> TestFeed {
+            TestFeed {
+                key: Pubkey::new_unique(),
+                lamports: 0,
+                buffer: vec![0; (len + 8) / 16 + 1],
+                len,
+            }
+        }
+        fn from_data(data: &[u8]) -> TestFeed {
+            let mut feed = TestFeed::zeroed(data.len());
+            feed.data().copy_from_slice(data);
+            feed
+        }
+        fn update_header(&mut self, f: impl FnOnce(&mut Transmissions)) {
+            let data = self.data();
+            let mut header = Transmissions::deserialize(&mut &data[..]).unwrap();
+            f(&mut header);
+            header.serialize(&mut &mut data[..]).unwrap();
+        }
+        fn data(&mut self) -> &mut [u8] {
+            &mut bytemuck::cast_slice_mut::<_, u8>(&mut self.buffer)[8..8 + self.len]
+        }
+        fn info(&mut self) -> AccountInfo<'_> {
+            let data = &mut bytemuck::cast_slice_mut::<_, u8>(&mut self.buffer)[8..8 + self.len];
+            AccountInfo::new(
+                &self.key,
+                false,
+                false,
+                &mut self.lamports,
+                data,
+                &CHAINLINK_STORE_PROGRAM,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn rounds(answers: impl IntoIterator<Item = i128>) -> Vec<Transmission> {
+        answers
+            .into_iter()
+            .zip(1..)
+            .map(|(answer, i)| Transmission::new(u64::from(i), i, answer))
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "client")]
+    fn test_to_csv() {
+        let mut feed = TestFeed::new(8, 5, 2, 3, &rounds(1..=20));
+        let csv = to_csv(&feed.info()).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("round_id,slot,timestamp,answer,scaled_answer")
+        );
+        // historical 10 and 15, then live 19 and 20
+        assert_eq!(lines.clone().count(), 4);
+        assert_eq!(lines.next(), Some("10,10,10,10,0.00000010"));
+        assert_eq!(lines.last(), Some("20,20,20,20,0.00000020"));
+
+        assert_eq!(format_decimal(-4200012345678, 8), "-42000.12345678");
+        assert_eq!(format_decimal(5, 0), "5");
+    }
+
+    #[test]
+    fn test_dump_all_rounds() {
+        let mut feed = TestFeed::new(8, 5, 2, 3, &rounds(1..=20));
+        let rounds = dump_all_rounds(&feed.info()).unwrap();
+        // historical 10 and 15, then live 19 and 20. the historical 20 is skipped
+        assert_eq!(
+            rounds
+                .iter()
+                .map(|round| (round.round_id, round.answer))
+                .collect::<Vec<_>>(),
+            vec![(10, 10), (15, 15), (19, 19), (20, 20)]
+        );
+        let mut feed = TestFeed::new(8, 5, 2, 3, &[]);
+        assert!(dump_all_rounds(&feed.info()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_answer_in_units() {
+        // 42000.12345678 with 8 decimals
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([4200012345678]));
+        assert_eq!(answer_in_units(&feed.info(), 6).unwrap(), 42000123456);
+        assert_eq!(answer_in_units(&feed.info(), 8).unwrap(), 4200012345678);
+        assert_eq!(
+            answer_in_units(&feed.info(), 18).unwrap(),
+            42000123456780000000000
+        );
+        assert_eq!(
+            answer_in_units(&feed.info(), 40).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([-1]));
+        assert_eq!(
+            answer_in_units(&feed.info(), 6).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_latest_answer_wad() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([4200012345678]));
+        assert_eq!(
+            latest_answer_wad(&feed.info()).unwrap(),
+            42000_123456780000000000
+        );
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([i128::MAX / 1_000]));
+        assert_eq!(
+            latest_answer_wad(&feed.info()).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_latest_answer_usd() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([4200012345678]));
+        assert_eq!(latest_answer_usd(&feed.info()).unwrap(), 4200012345678);
+        let mut feed = TestFeed::new(18, 5, 10, 3, &rounds([42000_123456789012345678]));
+        assert_eq!(latest_answer_usd(&feed.info()).unwrap(), 4200012345678);
+        let mut feed = TestFeed::new(6, 5, 10, 3, &rounds([42000_123456]));
+        assert_eq!(latest_answer_usd(&feed.info()).unwrap(), 4200012345600);
+        let mut feed = TestFeed::new(0, 5, 10, 3, &rounds([i128::MAX / 1_000]));
+        assert_eq!(
+            latest_answer_usd(&feed.info()).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_latest_price_ratio() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([4200012345678]));
+        assert_eq!(
+            latest_price_ratio(&feed.info()).unwrap(),
+            (4200012345678, 100_000_000)
+        );
+        let mut feed = TestFeed::new(18, 5, 10, 3, &rounds([1_500_000_000_000_000_000]));
+        assert_eq!(
+            latest_price_ratio(&feed.info()).unwrap(),
+            (1_500_000_000_000_000_000, 1_000_000_000_000_000_000)
+        );
+
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([0]));
+        assert_eq!(
+            latest_price_ratio(&feed.info()).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        let mut feed = TestFeed::new(39, 5, 10, 3, &rounds([1]));
+        assert_eq!(
+            latest_price_ratio(&feed.info()).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_within_deviation() {
+        // 42000 with 8 decimals
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([4200000000000]));
+        // the feed is 500 bps above 40000 with 6 decimals
+        assert!(within_deviation(&feed.info(), 40000000000, 6, 500).unwrap());
+        assert!(!within_deviation(&feed.info(), 40000000000, 6, 499).unwrap());
+        // and ~454.5 bps below 44000 with 18 decimals, truncated
+        let reference = 44000 * 10_i128.pow(18);
+        assert!(within_deviation(&feed.info(), reference, 18, 454).unwrap());
+        assert!(!within_deviation(&feed.info(), reference, 18, 453).unwrap());
+        assert_eq!(
+            within_deviation(&feed.info(), 0, 8, 50).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+        assert_eq!(
+            within_deviation(&feed.info(), 1, 40, 50).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_compare_feeds() {
+        // 42000 with 8 decimals against 41000 and 43000 with 6 decimals
+        let mut btc = TestFeed::new(8, 5, 10, 3, &rounds([4200000000000]));
+        let mut lower = TestFeed::new(6, 5, 10, 3, &rounds([41000000000]));
+        let mut higher = TestFeed::new(6, 5, 10, 3, &rounds([43000000000]));
+        assert_eq!(
+            compare_feeds(&btc.info(), &lower.info()).unwrap(),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_feeds(&btc.info(), &higher.info()).unwrap(),
+            Ordering::Less
+        );
+        let mut same = TestFeed::new(18, 5, 10, 3, &rounds([42000 * 10_i128.pow(18)]));
+        assert_eq!(
+            compare_feeds(&same.info(), &btc.info()).unwrap(),
+            Ordering::Equal
+        );
+        let mut huge = TestFeed::new(0, 5, 10, 3, &rounds([i128::MAX]));
+        assert_eq!(
+            compare_feeds(&huge.info(), &same.info()).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_latest_round_data_batch() {
+        let mut btc = TestFeed::new(8, 5, 10, 3, &rounds([100, 200]));
+        let mut eth = TestFeed::new(8, 5, 10, 3, &rounds([300]));
+        let mut empty = TestFeed::new(8, 5, 10, 3, &[]);
+        let (btc, eth, empty) = (btc.info(), eth.info(), empty.info());
+        let rounds = latest_round_data_batch(&[&btc, &eth]).unwrap();
+        assert_eq!(
+            rounds.iter().map(|round| round.answer).collect::<Vec<_>>(),
+            vec![200, 300]
+        );
+        assert_eq!(
+            latest_round_data_batch(&[&btc, &empty, &eth]).unwrap_err(),
+            SclinkError::NoRounds.into()
+        );
+    }
+
+    #[test]
+    fn test_latest_round_stamped() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100, 200]));
+        let stamped = latest_round_stamped(&feed.info(), 500, 1000).unwrap();
+        assert_eq!(stamped.round.round_id, 2);
+        assert_eq!((stamped.round.slot, stamped.round.timestamp), (2, 2));
+        assert_eq!(
+            (stamped.observed_slot, stamped.observed_timestamp),
+            (500, 1000)
+        );
+    }
+
+    #[test]
+    fn test_emit_bytes() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100, 200]));
+        let bytes = emit_bytes(&feed.info()).unwrap();
+        assert_eq!(
+            ChainlinkPriceEvent::DISCRIMINATOR,
+            solana_program::hash::hash(b"event:ChainlinkPriceEvent").to_bytes()[..8]
+        );
+        assert_eq!(bytes[..8], ChainlinkPriceEvent::DISCRIMINATOR);
+        // feed, round_id, answer, decimals, timestamp, slot
+        assert_eq!(bytes.len(), 8 + 32 + 4 + 16 + 1 + 4 + 8);
+        assert_eq!(bytes[8..40], feed.key.to_bytes());
+        assert_eq!(bytes[40..44], 2_u32.to_le_bytes());
+        assert_eq!(bytes[44..60], 200_i128.to_le_bytes());
+        assert_eq!(bytes[60], 8);
+        assert_eq!(bytes[61..65], 2_u32.to_le_bytes());
+        assert_eq!(bytes[65..73], 2_u64.to_le_bytes());
+        assert_eq!(
+            ChainlinkPriceEvent::try_from_slice(&bytes[8..]).unwrap(),
+            ChainlinkPriceEvent {
+                feed: feed.key,
+                round_id: 2,
+                answer: 200,
+                decimals: 8,
+                timestamp: 2,
+                slot: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_latest_round_checked() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100, 200]));
+        assert_eq!(
+            latest_round_checked(&feed.info(), 62, 60).unwrap().answer,
+            200
+        );
+        assert_eq!(
+            latest_round_checked(&feed.info(), 1, 0).unwrap().answer,
+            200
+        );
+        assert_eq!(
+            latest_round_checked(&feed.info(), 63, 60).unwrap_err(),
+            SclinkError::StalePrice.into()
+        );
+    }
+
+    #[test]
+    fn test_latest_answer_clamped() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100]));
+        assert_eq!(
+            latest_answer_clamped(&feed.info(), 50, 150).unwrap(),
+            (100, false)
+        );
+        assert_eq!(
+            latest_answer_clamped(&feed.info(), 120, 150).unwrap(),
+            (120, true)
+        );
+        assert_eq!(
+            latest_answer_clamped(&feed.info(), 50, 80).unwrap(),
+            (80, true)
+        );
+        assert_eq!(
+            latest_answer_clamped(&feed.info(), 80, 50).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_latest_interval_secs() {
+        let mut rounds = rounds([100, 101, 102]);
+        rounds[1].timestamp = 10;
+        rounds[2].timestamp = 70;
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds);
+        assert_eq!(latest_interval_secs(&feed.info()).unwrap(), Some(60));
+
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds[..1]);
+        assert_eq!(latest_interval_secs(&feed.info()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds([100, 200]));
+        feed.update_header(|header| header.description[..9].copy_from_slice(b"BTC / USD"));
+        let data = feed.data().to_vec();
+        assert_eq!(
+            latest_round_from_bytes(&data).unwrap(),
+            latest_round_data(&feed.info()).unwrap()
+        );
+        assert_eq!(decimals_from_bytes(&data).unwrap(), 8);
+        assert_eq!(description_from_bytes(&data).unwrap(), "BTC / USD");
+        assert_eq!(
+            decimals_from_bytes(&data[..100]).unwrap_err(),
+            SclinkError::TruncatedAccount.into()
+        );
+
+        let empty = TestFeed::new(8, 5, 4, 3, &[]).data().to_vec();
+        assert_eq!(
+            latest_round_from_bytes(&empty).unwrap_err(),
+            SclinkError::NoRounds.into()
+        );
+    }
+
+    #[test]
+    fn test_latest_answer_and_timestamp() {
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds([100, 200]));
+        assert_eq!(latest_answer(&feed.info()).unwrap(), 200);
+        assert_eq!(latest_timestamp(&feed.info()).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_round_range() {
+        let mut feed = TestFeed::new(8, 5, 2, 3, &rounds(1..=12));
+        // historical 5 and 10, live 11 and 12
+        assert_eq!(
+            round_range(&feed.info(), 0, 20)
+                .unwrap()
+                .iter()
+                .map(|round| (round.round_id, round.answer))
+                .collect::<Vec<_>>(),
+            vec![(5, 5), (10, 10), (11, 11), (12, 12)]
+        );
+        assert!(round_range(&feed.info(), 6, 9).unwrap().is_empty());
+        assert!(round_range(&feed.info(), 12, 11).unwrap().is_empty());
+        assert_eq!(
+            round_range_capped(&feed.info(), 3, 12, 10).unwrap().len(),
+            4
+        );
+        assert_eq!(
+            round_range_capped(&feed.info(), 2, 12, 10).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+        assert_eq!(
+            round_range(&feed.info(), 0, MAX_ROUND_RANGE).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_oldest_round_data() {
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds(1..=2));
+        let oldest = oldest_round_data(&feed.info()).unwrap();
+        assert_eq!((oldest.round_id, oldest.answer), (1, 1));
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds(1..=6));
+        let oldest = oldest_round_data(&feed.info()).unwrap();
+        assert_eq!(
+            (oldest.round_id, oldest.answer, oldest.timestamp),
+            (3, 3, 3)
+        );
+        let mut feed = TestFeed::new(8, 5, 4, 3, &[]);
+        assert_eq!(
+            oldest_round_data(&feed.info()).unwrap_err(),
+            SclinkError::NoRounds.into()
+        );
+    }
+
+    #[test]
+    fn test_live_time_span() {
+        let mut feed = TestFeed::new(8, 5, 4, 3, &[]);
+        assert_eq!(live_time_span(&feed.info()).unwrap(), None);
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds([1]));
+        assert_eq!(live_time_span(&feed.info()).unwrap(), Some((1, 1)));
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds(1..=9));
+        assert_eq!(live_time_span(&feed.info()).unwrap(), Some((6, 9)));
+    }
+
+    #[test]
+    fn test_round_timestamp() {
+        let mut feed = TestFeed::new(8, 5, 2, 3, &rounds(1..=12));
+        assert_eq!(round_timestamp(&feed.info(), 12).unwrap(), Some(12));
+        assert_eq!(round_timestamp(&feed.info(), 9).unwrap(), Some(5));
+        assert_eq!(round_timestamp(&feed.info(), 13).unwrap(), None);
+        assert_eq!(round_timestamp(&feed.info(), 4).unwrap(), None);
+    }
+
+    #[test]
+    fn test_nth_latest_round() {
+        let mut feed = TestFeed::new(8, 5, 2, 3, &rounds(1..=12));
+        let mut round = |back| nth_latest_round(&feed.info(), back).unwrap();
+        assert_eq!(round(0).map(|round| round.round_id), Some(12));
+        assert_eq!(round(1).map(|round| round.answer), Some(11));
+        // round 9 left the live buffer and rounds down to the sample of round 5
+        let rounded = round(3).unwrap();
+        assert_eq!((rounded.round_id, rounded.answer), (5, 5));
+        assert_eq!(round(8), None);
+        assert_eq!(round(12), None);
+        assert_eq!(round(u32::MAX), None);
+    }
+
+    #[test]
+    fn test_live_fill() {
+        let mut feed = TestFeed::new(8, 5, 4, 3, &[]);
+        assert_eq!(live_fill(&feed.info()).unwrap(), (0, 4));
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds(1..=3));
+        assert_eq!(live_fill(&feed.info()).unwrap(), (3, 4));
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds(1..=9));
+        assert_eq!(live_fill(&feed.info()).unwrap(), (4, 4));
+    }
+
+    #[test]
+    fn test_buffer_lengths() {
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds([1]));
+        assert_eq!(buffer_lengths(&feed.info()).unwrap(), (4, 3));
+        let mut feed = TestFeed::new(8, 5, 4, 0, &[]);
+        assert_eq!(buffer_lengths(&feed.info()).unwrap(), (4, 0));
+    }
+
+    #[test]
+    fn test_cursors() {
+        let mut feed = TestFeed::new(8, 5, 4, 3, &[]);
+        assert_eq!(cursors(&feed.info()).unwrap(), (0, 0));
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds(1..=6));
+        assert_eq!(cursors(&feed.info()).unwrap(), (2, 1));
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds(1..=20));
+        assert_eq!(cursors(&feed.info()).unwrap(), (0, 1));
+    }
+
+    #[test]
+    fn test_recent_rounds() {
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds(1..=6));
+        let recent = recent_rounds(&feed.info(), 3).unwrap();
+        assert_eq!(
+            recent
+                .iter()
+                .map(|round| (round.round_id, round.answer))
+                .collect::<Vec<_>>(),
+            vec![(6, 6), (5, 5), (4, 4)]
+        );
+        // clamped to the live buffer
+        assert_eq!(recent_rounds(&feed.info(), 10).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_twap() {
+        let transmission = |answer, timestamp| Transmission {
+            answer,
+            timestamp,
+            ..Default::default()
+        };
+        let mut feed = TestFeed::new(
+            8,
+            5,
+            10,
+            3,
+            &[
+                transmission(100, 10),
+                transmission(200, 20),
+                transmission(400, 40),
+            ],
+        );
+        // 200 for 20s, 100 for 10s
+        assert_eq!(twap(&feed.info(), 30).unwrap(), 166);
+        // 200 for 20s, 100 for 5s
+        assert_eq!(twap(&feed.info(), 25).unwrap(), 180);
+        // only 30s are available
+        assert_eq!(twap(&feed.info(), 100).unwrap(), 166);
+        assert_eq!(twap(&feed.info(), 0).unwrap(), 400);
+
+        let mut feed = TestFeed::new(8, 5, 10, 3, &[transmission(400, 40)]);
+        assert_eq!(twap(&feed.info(), 30).unwrap(), 400);
+
+        // the zero width interval of 100 is skipped
+        let mut feed = TestFeed::new(
+            8,
+            5,
+            10,
+            3,
+            &[
+                transmission(100, 10),
+                transmission(300, 10),
+                transmission(400, 20),
+            ],
+        );
+        assert_eq!(twap(&feed.info(), 100).unwrap(), 300);
+
+        let mut feed = TestFeed::new(8, 5, 10, 3, &[]);
+        assert_eq!(
+            twap(&feed.info(), 30).unwrap_err(),
+            SclinkError::NoRounds.into()
+        );
+    }
+
+    #[test]
+    fn test_price_change() {
+        let mut feed = TestFeed::new(8, 5, 2, 3, &rounds([0, 100, 110, 99, 50, 55]));
+        assert_eq!(price_change(&feed.info(), 5, 6).unwrap(), 5);
+        assert_eq!(price_change(&feed.info(), 6, 5).unwrap(), -5);
+        assert_eq!(price_change_bps(&feed.info(), 5, 6).unwrap(), 1000);
+        assert_eq!(price_change_bps(&feed.info(), 6, 5).unwrap(), -909);
+        assert_eq!(
+            price_change(&feed.info(), 6, 7).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([0, 100]));
+        assert_eq!(price_change(&feed.info(), 1, 2).unwrap(), 100);
+        assert_eq!(
+            price_change_bps(&feed.info(), 1, 2).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([i128::MIN, 1]));
+        assert_eq!(
+            price_change(&feed.info(), 1, 2).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_median_recent() {
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds([1000, 7, 3, 9, 5]));
+        assert_eq!(median_recent(&feed.info(), 3).unwrap(), 5);
+        // 3, 5, 7, 9
+        assert_eq!(median_recent(&feed.info(), 4).unwrap(), 6);
+        // clamped to the live buffer, 1000 has been overwritten
+        assert_eq!(median_recent(&feed.info(), 10).unwrap(), 6);
+        assert_eq!(median_recent(&feed.info(), 1).unwrap(), 5);
+
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds([-3, -4]));
+        assert_eq!(median_recent(&feed.info(), 2).unwrap(), -4);
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds([i128::MAX, i128::MAX - 2]));
+        assert_eq!(median_recent(&feed.info(), 2).unwrap(), i128::MAX - 1);
+
+        let mut feed = TestFeed::new(8, 5, 4, 3, &[]);
+        assert_eq!(
+            median_recent(&feed.info(), 3).unwrap_err(),
+            SclinkError::NoRounds.into()
+        );
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds([1]));
+        assert_eq!(
+            median_recent(&feed.info(), 0).unwrap_err(),
+            SclinkError::NoRounds.into()
+        );
+    }
+
+    #[test]
+    fn test_round_at_timestamp() {
+        let mut feed = TestFeed::new(8, 5, 2, 3, &rounds(1..=12));
+        let round = round_at_timestamp(&feed.info(), 11).unwrap().unwrap();
+        assert_eq!(
+            (round.round_id, round.answer, round.timestamp),
+            (11, 11, 11)
+        );
+        let round = round_at_timestamp(&feed.info(), 9).unwrap().unwrap();
+        assert_eq!((round.round_id, round.answer, round.timestamp), (5, 5, 5));
+        assert_eq!(round_at_timestamp(&feed.info(), 4).unwrap(), None);
+    }
+
+    #[test]
+    fn test_interpolated_answer() {
+        let transmissions = [(10, 100), (20, 200), (30, 150)]
+            .map(|(timestamp, answer)| Transmission::new(0, timestamp, answer));
+        let mut feed = TestFeed::new(8, 5, 10, 3, &transmissions);
+        let answer = |feed: &mut TestFeed, ts| interpolated_answer(&feed.info(), ts).unwrap();
+        assert_eq!(answer(&mut feed, 10), Some(100));
+        assert_eq!(answer(&mut feed, 15), Some(150));
+        assert_eq!(answer(&mut feed, 20), Some(200));
+        assert_eq!(answer(&mut feed, 27), Some(165));
+        assert_eq!(answer(&mut feed, 30), Some(150));
+        assert_eq!(answer(&mut feed, 9), None);
+        assert_eq!(answer(&mut feed, 31), None);
+        let mut empty = TestFeed::new(8, 5, 10, 3, &[]);
+        assert_eq!(answer(&mut empty, 10), None);
+    }
+
+    #[test]
+    fn test_sclink_error() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        let key = feed.key;
+        let mut lamports = 0;
+        let not_a_feed = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            feed.data(),
+            &key,
+            false,
+            0,
+        );
+        assert_eq!(
+            latest_round_data(&not_a_feed).unwrap_err(),
+            ProgramError::Custom(0)
+        );
+        assert_eq!(
+            ProgramError::from(SclinkError::StalePrice),
+            ProgramError::Custom(5)
+        );
+    }
+
+    #[test]
+    fn test_round_data_with_decimals() {
+        let mut feed = TestFeed::new(8, 5, 2, 3, &rounds(1..=12));
+        // live
+        let round = round_data_with_decimals(&feed.info(), 11).unwrap();
+        assert_eq!((round.round.round_id, round.round.answer), (11, 11));
+        assert_eq!(round.decimals, 8);
+        // historical
+        let round = round_data_with_decimals(&feed.info(), 5).unwrap();
+        assert_eq!((round.round.round_id, round.round.answer), (5, 5));
+        assert_eq!(
+            round_data_with_decimals(&feed.info(), 13).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_query_many() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100, 200]));
+        feed.update_header(|header| header.description[..9].copy_from_slice(b"BTC / USD"));
+        let scopes = [
+            Scope::Version,
+            Scope::Decimals,
+            Scope::Description,
+            Scope::LatestRoundData,
+        ];
+        let results = query_many(&feed.info(), &scopes).unwrap();
+        assert_eq!(
+            results,
+            scopes
+                .iter()
+                .map(|scope| query(&feed.info(), *scope).unwrap())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(results[2], b"BTC / USD");
+
+        feed.update_header(|header| header.version = 1);
+        assert_eq!(
+            query_many(&feed.info(), &[Scope::Version]).unwrap(),
+            vec![vec![1]]
+        );
+        assert_eq!(
+            query_many(&feed.info(), &scopes).unwrap_err(),
+            SclinkError::BadVersion.into()
+        );
+    }
+
+    #[test]
+    fn test_query_with_program() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        let key = feed.key;
+        let store_program = Pubkey::new_unique();
+        let mut lamports = 0;
+        let devnet_feed = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            feed.data(),
+            &store_program,
+            false,
+            0,
+        );
+        assert_eq!(
+            query_with_program(&devnet_feed, Scope::Decimals, &store_program).unwrap(),
+            vec![8]
+        );
+        assert_eq!(
+            query(&devnet_feed, Scope::Decimals).unwrap_err(),
+            SclinkError::InvalidOwner.into()
+        );
+    }
+
+    #[test]
+    fn test_feed_builder() {
+        let data = FeedBuilder::new()
+            .decimals(18)
+            .granularity(2)
+            .live_length(2)
+            .historical_length(3)
+            .description("ETH / USD")
+            .insert_round(rounds([100])[0])
+            .insert_round(rounds([200])[0])
+            .build();
+        assert_eq!(data.len(), 8 + HEADER_SIZE + 5 * size_of::<Transmission>());
+        let mut feed = TestFeed::from_data(&data);
+        assert_eq!(decimals(&feed.info()).unwrap(), 18);
+        assert_eq!(description(&feed.info()).unwrap(), "ETH / USD");
+        assert_eq!(latest_round_data(&feed.info()).unwrap().answer, 200);
+        with_store_readonly(&feed.info(), |store| {
+            assert_eq!(
+                store
+                    .historical_iter()
+                    .map(|round| round.answer)
+                    .sum::<i128>(),
+                200
+            );
+        })
+        .unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let round = RoundWithDecimals {
+            round: Round {
+                round_id: 2177184,
+                slot: 141757948,
+                timestamp: 1657926454,
+                answer: i128::MAX,
+            },
+            decimals: 8,
+        };
+        let json = serde_json::to_string(&round).unwrap();
+        assert_eq!(
+            json,
+            r#"{"round":{"round_id":2177184,"slot":141757948,"timestamp":1657926454,"answer":"170141183460469231731687303715884105727"},"decimals":8}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<RoundWithDecimals>(&json).unwrap(),
+            round
+        );
+    }
+
+    #[test]
+    fn test_round_conversions() {
+        let round = Round {
+            round_id: 1,
+            slot: 2,
+            timestamp: 3,
+            answer: 4,
+        };
+        let with_decimals = round.with_decimals(8);
+        assert_eq!(with_decimals, RoundWithDecimals { round, decimals: 8 });
+        assert_eq!(Round::from(with_decimals), round);
+    }
+
+    #[test]
+    fn test_round_ordering() {
+        let round = |round_id, slot| Round {
+            round_id,
+            slot,
+            timestamp: 0,
+            answer: 0,
+        };
+        let mut rounds = vec![round(3, 1), round(1, 9), round(2, 5), round(1, 2)];
+        rounds.sort();
+        assert_eq!(
+            rounds,
+            vec![round(1, 2), round(1, 9), round(2, 5), round(3, 1)]
+        );
+        assert_eq!(rounds.iter().max(), Some(&round(3, 1)));
+    }
+
+    #[test]
+    fn test_to_scaled() {
+        let round = Round {
+            round_id: 1,
+            slot: 1,
+            timestamp: 1,
+            answer: -4200012345678,
+        };
+        assert_eq!(round.with_decimals(8).to_scaled(8).unwrap(), -4200012345678);
+        assert_eq!(round.with_decimals(18).to_scaled(8).unwrap(), -420);
+        assert_eq!(
+            round.with_decimals(6).to_scaled(8).unwrap(),
+            -420001234567800
+        );
+        assert_eq!(
+            round.with_decimals(0).to_scaled(38).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_negative_answer() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([-4200012345678, i128::MIN]));
+        let raw = query(&feed.info(), Scope::LatestRoundData).unwrap();
+        assert_eq!(raw[raw.len() - 16..], i128::MIN.to_le_bytes());
+        assert_eq!(latest_round_data(&feed.info()).unwrap().answer, i128::MIN);
+
+        let round = round_data_with_decimals(&feed.info(), 1).unwrap();
+        assert_eq!(round.round.answer, -4200012345678);
+        assert!(round.is_negative());
+        assert_eq!(round.as_f64(), -42000.12345678);
+        let zero = Round {
+            answer: 0,
+            ..round.round
+        };
+        assert!(!zero.with_decimals(8).is_negative());
+    }
+
+    #[test]
+    fn test_format_answer() {
+        let round = |answer, decimals| {
+            Round {
+                round_id: 1,
+                slot: 1,
+                timestamp: 1,
+                answer,
+            }
+            .with_decimals(decimals)
+        };
+        assert_eq!(round(4200012345678, 8).format_answer(), "42000.12345678");
+        assert_eq!(round(-4200012345678, 8).format_answer(), "-42000.12345678");
+        assert_eq!(round(5, 8).format_answer(), "0.00000005");
+        assert_eq!(round(-5, 8).format_answer(), "-0.00000005");
+        assert_eq!(round(0, 2).format_answer(), "0.00");
+        assert_eq!(round(42, 0).format_answer(), "42");
+        assert_eq!(
+            round(i128::MIN, 18).format_answer(),
+            "-170141183460469231731.687303715884105728"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let round = Round {
+            round_id: 2177184,
+            slot: 141757948,
+            timestamp: 1657926454,
+            answer: 4200012000000,
+        };
+        assert_eq!(
+            round.to_string(),
+            "round 2177184 @ slot 141757948 ts 1657926454 answer 4200012000000"
+        );
+        assert_eq!(
+            RoundWithDecimals { round, decimals: 8 }.to_string(),
+            "round 2177184 @ slot 141757948 ts 1657926454 answer 42000.12000000"
+        );
+    }
+
+    #[test]
+    fn test_feed_version() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        assert_eq!(feed_version(&feed.info()).unwrap(), FeedVersion::V2);
+        feed.update_header(|header| header.version = 1);
+        assert_eq!(
+            feed_version(&feed.info()).unwrap_err(),
+            SclinkError::BadVersion.into()
+        );
+        assert_eq!(
+            latest_round_data(&feed.info()).unwrap_err(),
+            SclinkError::BadVersion.into()
+        );
+    }
+
+    #[test]
+    fn test_description_lossy() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        // no null terminator
+        feed.update_header(|header| header.description = [b'a'; 32]);
+        assert_eq!(description(&feed.info()).unwrap(), "a".repeat(32));
+        assert_eq!(description_lossy(&feed.info()).unwrap(), "a".repeat(32));
+
+        feed.update_header(|header| {
+            header.description = [0; 32];
+            header.description[..9].copy_from_slice(b"BTC / USD");
+            header.description[9] = 0xff;
+        });
+        assert_eq!(
+            description(&feed.info()).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+        assert_eq!(
+            description_lossy(&feed.info()).unwrap(),
+            "BTC / USD\u{fffd}"
+        );
+    }
+
+    #[test]
+    fn test_is_initialized() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &[]);
+        assert!(!is_initialized(&feed.info()).unwrap());
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        assert!(is_initialized(&feed.info()).unwrap());
+    }
+
+    #[test]
+    fn test_lowering_access_controller() {
+        let controller = Pubkey::new_unique();
+        let mut data = Store::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(controller.as_ref());
+        let mut store = TestFeed::from_data(&data);
+        assert_eq!(
+            lowering_access_controller(&store.info()).unwrap(),
+            controller
+        );
+
+        let mut truncated = TestFeed::from_data(&data[..100]);
+        assert_eq!(
+            lowering_access_controller(&truncated.info()).unwrap_err(),
+            SclinkError::TruncatedAccount.into()
+        );
+        // a feed is owned by the same program but isn't a store
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        assert_eq!(
+            lowering_access_controller(&feed.info()).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_store_program_id() {
+        assert_eq!(
+            CHAINLINK_STORE_PROGRAM.to_string(),
+            "HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny"
+        );
+    }
+
+    #[test]
+    fn test_owner() {
+        let multisig = Pubkey::new_unique();
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        feed.update_header(|header| {
+            header.owner = multisig;
+            header.proposed_owner = Pubkey::new_unique();
+        });
+        assert_eq!(owner(&feed.info()).unwrap(), multisig);
+    }
+
+    #[test]
+    fn test_granularity_and_flagging_threshold() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        feed.update_header(|header| header.flagging_threshold = 70_000);
+        assert_eq!(granularity(&feed.info()).unwrap(), 5);
+        assert_eq!(flagging_threshold(&feed.info()).unwrap(), 70_000);
+    }
+
+    #[test]
+    fn test_writer() {
+        let transmitter = Pubkey::new_unique();
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        feed.update_header(|header| header.writer = transmitter);
+        assert_eq!(writer(&feed.info()).unwrap(), transmitter);
+        assert_eq!(aggregator(&feed.info()).unwrap(), transmitter);
+    }
+
+    #[test]
+    fn test_feed_identity() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        feed.update_header(|header| header.description[..9].copy_from_slice(b"BTC / USD"));
+        assert_eq!(feed_identity(&feed.info()).unwrap(), "btc-usd-8");
+
+        feed.update_header(|header| {
+            header.decimals = 18;
+            header.description[..15].copy_from_slice(" stETH/ETH é  ".as_bytes());
+        });
+        assert_eq!(feed_identity(&feed.info()).unwrap(), "steth-eth-18");
+    }
+
+    #[test]
+    fn test_latest_round_data_with_decimals2() {
+        let mut feed = TestFeed::new(8, 5, 2, 3, &rounds(1..=5));
+        let latest = latest_round_data_with_decimals2(&feed.info()).unwrap();
+        assert_eq!(
+            latest,
+            latest_round_data_with_decimals(&feed.info()).unwrap()
+        );
+        assert_eq!((latest.round.round_id, latest.round.answer), (5, 5));
+
+        // a feed without any rounds errors instead of panicking
+        let mut feed = TestFeed::new(8, 5, 2, 3, &[]);
+        assert_eq!(
+            latest_round_data_with_decimals2(&feed.info()).unwrap_err(),
+            SclinkError::NoRounds.into()
+        );
+    }
+
+    #[test]
+    fn test_snapshot_lenient() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100]));
+        feed.update_header(|header| header.description[..3].copy_from_slice(&[0x42, 0xff, 0xfe]));
+        assert_eq!(
+            feed_snapshot(&feed.info()).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+        let snapshot = snapshot_lenient(&feed.info()).unwrap();
+        assert_eq!(snapshot.description, "");
+        assert_eq!((snapshot.decimals, snapshot.latest.answer), (8, 100));
+    }
+
+    #[test]
+    fn test_query_small_account() {
+        let mut feed = TestFeed::zeroed(8 + HEADER_SIZE - 1);
+        assert_eq!(
+            query(&feed.info(), Scope::Version).unwrap_err(),
+            SclinkError::TruncatedAccount.into()
+        );
+        assert_eq!(
+            latest_round_data(&feed.info()).unwrap_err(),
+            SclinkError::TruncatedAccount.into()
+        );
+    }
+
+    #[test]
+    fn test_query_version_mismatch() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100]));
+        feed.update_header(|header| header.version = 1);
+        assert_eq!(version(&feed.info()).unwrap(), 1);
+        assert_eq!(
+            decimals(&feed.info()).unwrap_err(),
+            SclinkError::BadVersion.into()
+        );
+        assert_eq!(
+            aggregator(&feed.info()).unwrap_err(),
+            SclinkError::BadVersion.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_feed() {
+        let aggregator = Pubkey::new_unique();
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        feed.update_header(|header| {
+            header.writer = aggregator;
+            header.description[..9].copy_from_slice(b"BTC / USD");
+        });
+        verify_feed(&feed.info(), &aggregator, "BTC / USD").unwrap();
+        assert_eq!(
+            verify_feed(&feed.info(), &Pubkey::new_unique(), "BTC / USD").unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(
+            verify_feed(&feed.info(), &aggregator, "ETH / USD").unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_verify_against_manifest() {
+        let transmitter = Pubkey::new_unique();
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100]));
+        feed.update_header(|header| {
+            header.writer = transmitter;
+            header.description[..9].copy_from_slice(b"BTC / USD");
+        });
+        let manifest = FeedManifest {
+            aggregator: transmitter,
+            decimals: 8,
+            description: "BTC / USD".to_string(),
+            writer: transmitter,
+        };
+        verify_against_manifest(&feed.info(), &manifest).unwrap();
+
+        // the writer is the aggregator field, so a distinct writer can't match
+        let distinct_writer = FeedManifest {
+            writer: Pubkey::new_unique(),
+            ..manifest.clone()
+        };
+        assert_eq!(
+            verify_against_manifest(&feed.info(), &distinct_writer).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        let manifest = FeedManifest {
+            description: "ETH / USD".to_string(),
+            ..manifest
+        };
+        assert_eq!(
+            verify_against_manifest(&feed.info(), &manifest).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_as_f64() {
+        let round = RoundWithDecimals {
+            round: Round {
+                round_id: 1,
+                slot: 1,
+                timestamp: 1,
+                answer: 4200012000000,
+            },
+            decimals: 8,
+        };
+        assert_eq!(round.as_f64(), 42000.12);
+        let round = RoundWithDecimals {
+            round: Round {
+                answer: -150,
+                ..round.round
+            },
+            decimals: 2,
+        };
+        assert_eq!(round.as_f64(), -1.5);
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn test_as_decimal() {
+        use std::str::FromStr;
+        let scaled = |answer, decimals| RoundWithDecimals {
+            round: Round {
+                round_id: 1,
+                slot: 1,
+                timestamp: 1,
+                answer,
+            },
+            decimals,
+        };
+        assert_eq!(
+            scaled(-4200012345678, 8).as_decimal().unwrap(),
+            rust_decimal::Decimal::from_str("-42000.12345678").unwrap()
+        );
+        assert_eq!(
+            scaled(1_234_567_890_000_000_000, 18).as_decimal().unwrap(),
+            rust_decimal::Decimal::from_str("1.23456789").unwrap()
+        );
+        assert!(scaled(1, 29).as_decimal().is_err());
+    }
+
+    #[test]
+    fn test_feed_metadata() {
+        let transmitter = Pubkey::new_unique();
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100]));
+        feed.update_header(|header| {
+            header.writer = transmitter;
+            header.description.copy_from_slice(&[b'x'; 32]);
+        });
+        assert_eq!(
+            feed_metadata(&feed.info()).unwrap(),
+            FeedMetadata {
+                version: FEED_VERSION,
+                decimals: 8,
+                description: "x".repeat(32),
+                aggregator: transmitter,
+            }
+        );
+    }
+
+    #[test]
+    fn test_is_stalled() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100, 101, 101, 101]));
+        assert!(is_stalled(&feed.info(), 3).unwrap());
+        assert!(!is_stalled(&feed.info(), 4).unwrap());
+        assert!(!is_stalled(&feed.info(), 1).unwrap());
+        // fewer rounds than the lookback
+        assert!(!is_stalled(&feed.info(), 5).unwrap());
+        // the same answer without ticking timestamps isn't a stalled writer
+        let transmissions = [(1, 100), (2, 100), (2, 100)]
+            .map(|(timestamp, answer)| Transmission::new(0, timestamp, answer));
+        let mut feed = TestFeed::new(8, 5, 10, 3, &transmissions);
+        assert!(!is_stalled(&feed.info(), 3).unwrap());
+        assert!(!is_stalled(&feed.info(), 2).unwrap());
+    }
+
+    #[test]
+    fn test_health_many() {
+        let mut healthy = TestFeed::new(8, 5, 10, 3, &rounds([100, 101, 102]));
+        let mut flagged = TestFeed::new(8, 5, 10, 3, &rounds([100, 100, 100]));
+        flagged.update_header(|header| header.state = Transmissions::FLAGGED);
+        let mut invalid = TestFeed::zeroed(8);
+        let (healthy, flagged, invalid) = (healthy.info(), flagged.info(), invalid.info());
+
+        let results = health_many(&[&healthy, &flagged, &invalid], 10, 3);
+        assert_eq!(
+            results[0],
+            Ok(FeedHealth {
+                flagged: false,
+                age_secs: 7,
+                stuck: false,
+            })
+        );
+        assert_eq!(
+            results[1],
+            Ok(FeedHealth {
+                flagged: true,
+                age_secs: 7,
+                stuck: true,
+            })
+        );
+        assert_eq!(results[2], Err(SclinkError::TruncatedAccount.into()));
+
+        // fewer rounds than requested can't be considered stuck
+        assert!(!health(&flagged, 10, 4).unwrap().stuck);
+    }
+
+    #[test]
+    fn test_latest_transmission_raw() {
+        let mut rounds = rounds([100, -4200012345678]);
+        rounds[1]._padding1 = u64::MAX;
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds);
+        let raw = latest_transmission_raw(&feed.info()).unwrap();
+        assert_eq!(raw.len(), size_of::<Transmission>());
+        assert_eq!(
+            i128::from_le_bytes(raw[16..32].try_into().unwrap()),
+            -4200012345678
+        );
+        assert_eq!(raw[32..40], [0xff; 8]);
+
+        let mut feed = TestFeed::new(8, 5, 10, 3, &[]);
+        assert_eq!(
+            latest_transmission_raw(&feed.info()).unwrap_err(),
+            SclinkError::NoRounds.into()
+        );
+    }
+
+    #[test]
+    fn test_with_store_readonly() {
+        let mut feed = TestFeed::new(8, 5, 2, 3, &rounds(1..=20));
+        let info = feed.info();
+        let latest = store::with_store(&info, |store| store.latest()).unwrap();
+        assert_eq!(latest.unwrap().answer, 20);
+        assert_eq!(
+            store::with_store_readonly(&info, |store| store.latest()).unwrap(),
+            latest
+        );
+        assert_eq!(
+            store::with_store_readonly(&info, |store| store.fetch(15)).unwrap(),
+            store::with_store(&info, |store| store.fetch(15)).unwrap()
+        );
+
+        // unaligned account data falls back to copying the buffers
+        let mut data = info.try_borrow_data().unwrap().to_vec();
+        let mut lamports = 0;
+        let key = Pubkey::new_unique();
+        let unaligned = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &CHAINLINK_STORE_PROGRAM,
+            false,
+            0,
+        );
+        assert_eq!(
+            store::with_store_readonly(&unaligned, |store| store.latest()).unwrap(),
+            latest
+        );
+        assert_eq!(latest_round_data(&unaligned).unwrap().answer, 20);
+    }
+
+    #[test]
+    fn test_with_decimals2_unaligned() {
+        let mut feed = TestFeed::new(8, 5, 4, 3, &rounds(1..=6));
+        let aligned = latest_round_data_with_decimals2(&feed.info()).unwrap();
+        // starting the data on a 16 byte boundary leaves the transmissions at offset 200
+        // only 8 byte aligned, like rpc fetched account data can be
+        let data = feed.data().to_vec();
+        let mut buffer = vec![0_u128; data.len() / 16 + 1];
+        let bytes = &mut bytemuck::cast_slice_mut::<_, u8>(&mut buffer)[..data.len()];
+        bytes.copy_from_slice(&data);
+        assert!(bytemuck::try_cast_slice::<_, Transmission>(&bytes[8 + HEADER_SIZE..]).is_err());
+        let mut lamports = 0;
+        let key = Pubkey::new_unique();
+        let unaligned = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            bytes,
+            &CHAINLINK_STORE_PROGRAM,
+            false,
+            0,
+        );
+        assert_eq!(
+            latest_round_data_with_decimals2(&unaligned).unwrap(),
+            aligned
+        );
+        assert_eq!(aligned.round.answer, 6);
+    }
+
+    #[test]
+    fn test_shorter_than_declared() {
+        let mut feed = TestFeed::new(8, 5, 4, 0, &rounds(1..=3));
+        let data = feed.data().to_vec();
+        // drop half of the declared live buffer, including the latest round
+        let mut truncated =
+            TestFeed::from_data(&data[..data.len() - 2 * size_of::<Transmission>()]);
+        let info = truncated.info();
+        assert_eq!(
+            latest_round_data(&info).unwrap_err(),
+            ProgramError::AccountDataTooSmall
+        );
+        assert_eq!(
+            latest_round_data_with_decimals2(&info).unwrap_err(),
+            ProgramError::AccountDataTooSmall
+        );
+        assert_eq!(
+            latest_round_fast(&info).unwrap_err(),
+            ProgramError::AccountDataTooSmall
+        );
+    }
+
+    #[test]
+    fn test_latest_round_fast() {
+        // live buffer sized like the mainnet feeds
+        let mut feed = TestFeed::new(8, 30, 86400, 100, &rounds(1..=86500));
+        let info = feed.info();
+        assert_eq!(
+            latest_round_fast(&info).unwrap(),
+            latest_round_data(&info).unwrap()
+        );
+
+        let mut feed = TestFeed::new(8, 5, 10, 3, &[]);
+        assert_eq!(
+            latest_round_fast(&feed.info()).unwrap_err(),
+            SclinkError::NoRounds.into()
+        );
+    }
+
+    #[test]
+    fn test_config_drifted() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100]));
+        feed.update_header(|header| header.description[..9].copy_from_slice(b"BTC / USD"));
+        assert!(!config_drifted(&feed.info(), 8, "BTC / USD").unwrap());
+        assert!(config_drifted(&feed.info(), 6, "BTC / USD").unwrap());
+        assert!(config_drifted(&feed.info(), 8, "BTC / USDC").unwrap());
+        assert!(config_drifted(&feed.info(), 8, "BTC").unwrap());
+    }
+
+    #[test]
+    fn test_feed_state() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        assert_eq!(feed_state(&feed.info()).unwrap(), FeedState::Normal);
+        feed.update_header(|header| header.state = Transmissions::FLAGGED);
+        assert_eq!(feed_state(&feed.info()).unwrap(), FeedState::Flagged);
+        feed.update_header(|header| header.state = 7);
+        assert_eq!(feed_state(&feed.info()).unwrap(), FeedState::Unknown(7));
+    }
+
+    #[test]
+    fn test_is_flagged() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([1]));
+        assert!(!is_flagged(&feed.info()).unwrap());
+        feed.update_header(|header| header.state = Transmissions::FLAGGED);
+        assert!(is_flagged(&feed.info()).unwrap());
+    }
+
+    #[test]
+    fn test_feed_snapshot_state() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100]));
+        assert_eq!(
+            feed_snapshot(&feed.info()).unwrap().state,
+            FeedState::Normal
+        );
+        feed.update_header(|header| header.state = Transmissions::FLAGGED);
+        let snapshot = feed_snapshot(&feed.info()).unwrap();
+        assert_eq!(
+            (snapshot.state, snapshot.latest.answer),
+            (FeedState::Flagged, 100)
+        );
+    }
+
+    #[test]
+    fn test_snapshot_with_raw() {
+        let mut feed = TestFeed::new(8, 5, 10, 3, &rounds([100, 200, 300]));
+        feed.update_header(|header| header.description[..9].copy_from_slice(b"BTC / USD"));
+        let (snapshot, raw) = snapshot_with_raw(&feed.info()).unwrap();
+        assert_eq!(snapshot.description, "BTC / USD");
+        assert_eq!(snapshot.latest.answer, 300);
+
+        let mut reparsed = TestFeed::from_data(&raw);
+        assert_eq!(feed_snapshot(&reparsed.info()).unwrap(), snapshot);
+    }
+
     #[test]
+    #[cfg(feature = "client")]
     fn test_query() {
         let rpc = solana_client::rpc_client::RpcClient::new("https://ssc-dao.genesysgo.net");
         let btc_feed = static_pubkey!("CGmWwBNsTRDENT5gmVZzRu38GnNnMm1K5C3sFiUUyYQX");
@@ -261,6 +3080,7 @@ mod test {
             static_pubkey!("8xfHq5ZctheZMhntmXsayHg4GtRGvDqdz4zKcjCqJgaY")
         );
         assert_eq!(description, "BTC / USD");
+        assert_eq!(feed_identity(&btc_feed_info).unwrap(), "btc-usd-8");
         assert!(latest_data.round_id >= 2177184);
         assert!(latest_data.slot >= 141757948);
         assert!(latest_data.timestamp >= 1657926454);
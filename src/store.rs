@@ -1,7 +1,7 @@
 //! store account types, values, etc.. extracted from https://github.com/smartcontractkit/chainlink-solana/blob/develop/contracts/programs/store/src/lib.rs
-use crate::FEED_VERSION;
+use crate::error::SclinkError;
+use crate::FeedVersion;
 use borsh::{BorshDeserialize, BorshSerialize};
-use so_defi_utils::accessor::{to_u32, AccessorType};
 use solana_program::account_info::AccountInfo;
 use solana_program::msg;
 use solana_program::program_error::ProgramError;
@@ -9,6 +9,9 @@ use solana_program::pubkey::Pubkey;
 
 pub const HEADER_SIZE: usize = 192;
 
+/// length of a year used when annualizing rates, leap years are ignored
+const SECONDS_PER_YEAR: i128 = 365 * 24 * 60 * 60;
+
 #[repr(C)]
 pub struct Store {
     pub __discriminator: [u8; 8],
@@ -17,6 +20,49 @@ pub struct Store {
     pub lowering_access_controller: Pubkey,
 }
 
+impl Store {
+    /// anchor's account discriminator, the first 8 bytes of `sha256("account:Store")`
+    pub const DISCRIMINATOR: [u8; 8] = [130, 48, 247, 244, 182, 191, 30, 26];
+    pub const OWNER_OFFSET: usize = 8;
+    pub const PROPOSED_OWNER_OFFSET: usize = 8 + 32;
+    /// discriminator, owner and proposed owner precede the lowering access controller
+    pub const LOWERING_ACCESS_CONTROLLER_OFFSET: usize = 8 + 32 + 32;
+    /// size of the account data, discriminator included
+    pub const LEN: usize = 8 + 32 * 3;
+}
+
+/// Returns the owner of a store account.
+pub fn read_store_owner(store: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    read_store_pubkey(store, Store::OWNER_OFFSET)
+}
+
+/// Returns the proposed owner of a store account, the default pubkey if there is none.
+pub fn read_proposed_owner(store: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    read_store_pubkey(store, Store::PROPOSED_OWNER_OFFSET)
+}
+
+/// Returns the access controller allowed to lower the flagging threshold of the feeds
+/// managed by a store account.
+pub fn read_lowering_access_controller(store: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    read_store_pubkey(store, Store::LOWERING_ACCESS_CONTROLLER_OFFSET)
+}
+
+/// validates that the account is a store, rather than a feed owned by the same program,
+/// and reads the pubkey at `offset`
+fn read_store_pubkey(store: &AccountInfo, offset: usize) -> Result<Pubkey, ProgramError> {
+    crate::check_owner(store)?;
+    let data = store.try_borrow_data()?;
+    if !data.starts_with(&Store::DISCRIMINATOR) {
+        msg!("not a store account");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if data.len() < Store::LEN {
+        msg!("account data too small");
+        return Err(SclinkError::TruncatedAccount.into());
+    }
+    crate::read_pubkey(&data[offset..offset + 32])
+}
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct NewTransmission {
@@ -37,28 +83,47 @@ pub struct Transmission {
     pub _padding2: u64,
 }
 
-use std::cell::Ref;
+impl Transmission {
+    /// Returns a transmission with zeroed padding.
+    pub fn new(slot: u64, timestamp: u32, answer: i128) -> Self {
+        Transmission {
+            slot,
+            timestamp,
+            answer,
+            ..Default::default()
+        }
+    }
+}
 
+use std::borrow::Cow;
 use std::mem::size_of;
 
+/// Reasons [`Feed::fetch_exact`] can't return the requested round.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub enum FetchError {
+    /// the round is in the future or no longer retained
+    NotFound,
+    /// the round left the live buffer and only the historical sample it rounds down to
+    /// is retained
+    RoundedToHistorical { returned_round_id: u32 },
+}
+
 #[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
 /// Two ringbuffers
 /// - Live one that has a day's worth of data that's updated every second
 /// - Historical one that stores historical data
 pub struct Feed<'a> {
     pub header: &'a mut Transmissions,
-    live: &'a mut [Transmission],
-    historical: &'a mut [Transmission],
+    live: Cow<'a, [Transmission]>,
+    historical: Cow<'a, [Transmission]>,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 // note about the type layout: it incorrectly detects a padding of 20 bytes at the start
 // and 151 bytes in the middle, subtract those values from the offsets
-#[cfg_attr(
-    not(target_arch = "bpf"),
-    derive(Debug),
-    derive(type_layout::TypeLayout)
-)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+#[cfg_attr(test, derive(type_layout::TypeLayout))]
 pub struct Transmissions {
     pub _discriminator: [u8; 8], // 8
     pub version: u8,             // 16
@@ -82,6 +147,140 @@ impl Transmissions {
     pub const FLAGGED: u8 = 1;
 }
 
+/// The discriminator and header exactly as laid out in the account data, for reading the
+/// header without copying it. Unlike [`Transmissions`] there is no padding between the
+/// fields, so the u32 fields are unaligned and stored as little endian bytes. The reserved
+/// bytes are the unused space up to the transmissions, which start at 200.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct TransmissionsHeaderRaw {
+    pub _discriminator: [u8; 8],     // 0
+    pub version: u8,                 // 8
+    pub state: u8,                   // 9
+    pub owner: Pubkey,               // 10
+    pub proposed_owner: Pubkey,      // 42
+    pub writer: Pubkey,              // 74
+    pub description: [u8; 32],       // 106
+    pub decimals: u8,                // 138
+    pub flagging_threshold: [u8; 4], // 139
+    pub latest_round_id: [u8; 4],    // 143
+    pub granularity: u8,             // 147
+    pub live_length: [u8; 4],        // 148
+    pub live_cursor: [u8; 4],        // 152
+    pub historical_cursor: [u8; 4],  // 156
+    pub _reserved0: [u8; 32],        // 160
+    pub _reserved1: [u8; 8],         // 192
+}
+
+const _: () = assert!(size_of::<TransmissionsHeaderRaw>() == 8 + HEADER_SIZE);
+
+impl TransmissionsHeaderRaw {
+    pub fn flagging_threshold(&self) -> u32 {
+        u32::from_le_bytes(self.flagging_threshold)
+    }
+    pub fn latest_round_id(&self) -> u32 {
+        u32::from_le_bytes(self.latest_round_id)
+    }
+    pub fn live_length(&self) -> u32 {
+        u32::from_le_bytes(self.live_length)
+    }
+    pub fn live_cursor(&self) -> u32 {
+        u32::from_le_bytes(self.live_cursor)
+    }
+    pub fn historical_cursor(&self) -> u32 {
+        u32::from_le_bytes(self.historical_cursor)
+    }
+}
+
+/// Casts the start of the account data to the raw header without copying. Only the length
+/// is checked, the version and buffers are left to the caller.
+pub fn header_ref(data: &[u8]) -> Result<&TransmissionsHeaderRaw, ProgramError> {
+    match data.get(..size_of::<TransmissionsHeaderRaw>()) {
+        Some(header) => Ok(bytemuck::from_bytes(header)),
+        None => {
+            msg!("account data too small");
+            Err(SclinkError::TruncatedAccount.into())
+        }
+    }
+}
+
+/// Copies the raw header out of the account data, for reading the fixed offsets without
+/// holding a borrow of the account.
+pub(crate) fn read_header_raw(
+    account: &AccountInfo,
+) -> Result<TransmissionsHeaderRaw, ProgramError> {
+    Ok(*header_ref(&account.try_borrow_data()?)?)
+}
+
+/// Ensures the account is large enough to hold the discriminator and header, so the
+/// fixed header offsets can be read.
+pub(crate) fn check_data_len(account: &AccountInfo) -> Result<(), ProgramError> {
+    if account.data_len() < 8 + HEADER_SIZE {
+        msg!("account data too small");
+        return Err(SclinkError::TruncatedAccount.into());
+    }
+    Ok(())
+}
+
+/// validates the version and returns the live length declared in the header
+fn live_length(account: &AccountInfo) -> Result<usize, ProgramError> {
+    check_data_len(account)?;
+    let header = read_header_raw(account)?;
+    FeedVersion::try_from(header.version)?;
+    Ok(header.live_length() as usize)
+}
+
+/// splits the transmissions following the header into the live and historical buffers.
+/// an empty live buffer is rejected as the ring buffer arithmetic would divide by zero,
+/// and an account shorter than the declared live buffer with `AccountDataTooSmall`
+fn split_buffers(data: &[u8], n: usize) -> Result<(&[u8], &[u8]), ProgramError> {
+    // skip the discriminator and header
+    let buffers = data.get(8 + HEADER_SIZE..).unwrap_or_default();
+    if n == 0 || data.len() < 8 + HEADER_SIZE || buffers.len() % size_of::<Transmission>() != 0 {
+        msg!("invalid transmissions length");
+        return Err(SclinkError::TruncatedAccount.into());
+    }
+    match n
+        .checked_mul(size_of::<Transmission>())
+        .filter(|live_len| *live_len <= buffers.len())
+    {
+        Some(live_len) => Ok(buffers.split_at(live_len)),
+        None => {
+            msg!("account data shorter than the declared live length");
+            Err(ProgramError::AccountDataTooSmall)
+        }
+    }
+}
+
+/// deserializes the header, failing instead of panicking on malformed data
+fn read_header(data: &[u8]) -> Result<Transmissions, ProgramError> {
+    match Transmissions::deserialize(&mut &data[..]) {
+        Ok(header) => Ok(header),
+        Err(_) => {
+            msg!("failed to deserialize transmissions");
+            Err(SclinkError::CastFailed.into())
+        }
+    }
+}
+
+/// account data isn't guaranteed to be aligned for Transmission, so read each one unaligned
+fn read_unaligned(buffer: &[u8]) -> Vec<Transmission> {
+    buffer
+        .chunks_exact(size_of::<Transmission>())
+        .map(bytemuck::pod_read_unaligned)
+        .collect()
+}
+
+/// borrows the transmissions straight from the account data, copying them instead if the
+/// data isn't aligned for `Transmission`
+fn borrow_transmissions(buffer: &[u8]) -> Cow<'_, [Transmission]> {
+    match bytemuck::try_cast_slice::<_, Transmission>(buffer) {
+        Ok(buffer) => Cow::Borrowed(buffer),
+        Err(_) => Cow::Owned(read_unaligned(buffer)),
+    }
+}
+
 pub fn with_store<'a, 'info: 'a, F, T>(
     account: &AccountInfo<'info>,
     f: F,
@@ -89,52 +288,147 @@ pub fn with_store<'a, 'info: 'a, F, T>(
 where
     F: FnOnce(&mut Feed) -> T,
 {
-    let n = {
-        let response = AccessorType::U8(8).access(account);
-        if response[0].ne(&FEED_VERSION) {
-            msg!("invalid feed version");
-            return Err(ProgramError::InvalidAccountData);
-        }
-        to_u32(&AccessorType::U32(148).access(account)[..]) as usize
-    };
-
-    let (live, historical) = {
-        let data = account.try_borrow_data()?;
-        let (live, hist) = Ref::map_split(data, |data| {
-            // skip the header
-            let (_header, data) = data.split_at(8 + HEADER_SIZE); // discriminator + header size
-            let (live, historical) = data.split_at(n * size_of::<Transmission>());
-            // NOTE: no try_map_split available..
-            let live = bytemuck::try_cast_slice::<_, Transmission>(live).unwrap();
-            let historical = bytemuck::try_cast_slice::<_, Transmission>(historical).unwrap();
-            (live, historical)
-        });
-        (live, hist)
-    };
+    let n = live_length(account)?;
     let data = account.try_borrow_data()?;
-    let mut transmission = Transmissions::deserialize(&mut &data[..]).unwrap();
+    let (live, historical) = split_buffers(&data, n)?;
+    let mut transmission = read_header(&data)?;
     let mut store = Feed {
         header: &mut transmission,
-        live: &mut live.to_vec(),
-        historical: &mut historical.to_vec(),
+        live: Cow::Owned(read_unaligned(live)),
+        historical: Cow::Owned(read_unaligned(historical)),
     };
     Ok(f(&mut store))
 }
 
+/// Same as [`with_store`] for read only access. Instead of copying, the ring buffers are
+/// borrowed straight from the account data, unless the data isn't aligned for
+/// `Transmission` in which case they are copied.
+pub fn with_store_readonly<'a, 'info: 'a, F, T>(
+    account: &AccountInfo<'info>,
+    f: F,
+) -> Result<T, ProgramError>
+where
+    F: FnOnce(&Feed) -> T,
+{
+    with_store_bytes(&account.try_borrow_data()?, f)
+}
+
+/// Same as [`with_store_readonly`] for raw account data, e.g. from an archived account
+/// dump. There's no account to check the owner of, so the data has to be trusted.
+pub fn with_store_bytes<F, T>(data: &[u8], f: F) -> Result<T, ProgramError>
+where
+    F: FnOnce(&Feed) -> T,
+{
+    if data.len() < 8 + HEADER_SIZE {
+        msg!("account data too small");
+        return Err(SclinkError::TruncatedAccount.into());
+    }
+    FeedVersion::try_from(data[8])?;
+    let mut transmission = read_header(data)?;
+    let (live, historical) = split_buffers(data, transmission.live_length as usize)?;
+    let store = Feed {
+        header: &mut transmission,
+        live: borrow_transmissions(live),
+        historical: borrow_transmissions(historical),
+    };
+    Ok(f(&store))
+}
+
+/// The ring buffer state of a feed decoded once, for reading many rounds out of the same
+/// account data without parsing and validating the header on every read.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct FeedView {
+    pub decimals: u8,
+    pub granularity: u8,
+    pub live_length: u32,
+    pub latest_round_id: u32,
+    pub live_cursor: u32,
+    pub historical_cursor: u32,
+}
+
+impl FeedView {
+    pub fn load(account: &AccountInfo) -> Result<FeedView, ProgramError> {
+        crate::check_owner(account)?;
+        let n = live_length(account)?;
+        let data = account.try_borrow_data()?;
+        split_buffers(&data, n)?;
+        let header = read_header(&data)?;
+        Ok(FeedView {
+            decimals: header.decimals,
+            granularity: header.granularity,
+            live_length: header.live_length,
+            latest_round_id: header.latest_round_id,
+            live_cursor: header.live_cursor,
+            historical_cursor: header.historical_cursor,
+        })
+    }
+
+    /// runs `f` over the ring buffers in `data`, which must be the data of the account the
+    /// view was loaded from
+    fn with_feed<T>(&self, data: &[u8], f: impl FnOnce(&Feed) -> T) -> Result<T, ProgramError> {
+        let (live, historical) = split_buffers(data, self.live_length as usize)?;
+        // only the ring buffer state is needed to locate rounds
+        let mut header = Transmissions {
+            _discriminator: [0; 8],
+            version: FeedVersion::V2 as u8,
+            state: Transmissions::NORMAL,
+            owner: Pubkey::default(),
+            proposed_owner: Pubkey::default(),
+            writer: Pubkey::default(),
+            description: [0; 32],
+            decimals: self.decimals,
+            flagging_threshold: 0,
+            latest_round_id: self.latest_round_id,
+            granularity: self.granularity,
+            live_length: self.live_length,
+            live_cursor: self.live_cursor,
+            historical_cursor: self.historical_cursor,
+        };
+        Ok(f(&Feed {
+            header: &mut header,
+            live: borrow_transmissions(live),
+            historical: borrow_transmissions(historical),
+        }))
+    }
+
+    /// Same as [`Feed::latest`], reading from the account data.
+    pub fn latest(&self, data: &[u8]) -> Result<Option<Transmission>, ProgramError> {
+        self.with_feed(data, |feed| feed.latest())
+    }
+
+    /// Same as [`Feed::fetch`], reading from the account data.
+    pub fn fetch(&self, data: &[u8], round_id: u32) -> Result<Option<Transmission>, ProgramError> {
+        self.with_feed(data, |feed| feed.fetch(round_id))
+    }
+}
+
 impl<'a> Feed<'a> {
-    pub fn insert(&mut self, round: Transmission) {
-        self.header.latest_round_id += 1;
+    /// Appends a round to the live buffer, and to the historical buffer on multiples of the
+    /// granularity. Fails with `ArithmeticOverflow`, leaving the feed untouched, once the
+    /// round id would pass `u32::MAX`. A granularity of zero never writes historical samples.
+    pub fn insert(&mut self, round: Transmission) -> Result<(), ProgramError> {
+        let latest_round_id = self
+            .header
+            .latest_round_id
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.header.latest_round_id = latest_round_id;
 
         // insert into live data
-        self.live[self.header.live_cursor as usize] = round;
+        self.live.to_mut()[self.header.live_cursor as usize] = round;
         self.header.live_cursor = (self.header.live_cursor + 1) % self.live.len() as u32;
 
-        if self.header.latest_round_id % self.header.granularity as u32 == 0 {
+        // feeds without historical slots only keep the live data
+        if latest_round_id.checked_rem(self.header.granularity as u32) == Some(0)
+            && !self.historical.is_empty()
+        {
             // insert into historical data
-            self.historical[self.header.historical_cursor as usize] = round;
+            self.historical.to_mut()[self.header.historical_cursor as usize] = round;
             self.header.historical_cursor =
                 (self.header.historical_cursor + 1) % self.historical.len() as u32;
         }
+        Ok(())
     }
 
     pub fn latest(&self) -> Option<Transmission> {
@@ -143,12 +437,211 @@ impl<'a> Feed<'a> {
         }
 
         let len = self.header.live_length;
-        // Handle wraparound
+        // the cursor points one past the latest insertion whether or not the buffer is full,
+        // so this never lands on an unpopulated slot. Handle wraparound
         let i = (self.header.live_cursor + len.saturating_sub(1)) % len;
 
         Some(self.live[i as usize])
     }
 
+    /// Returns the oldest populated live transmission, accounting for whether the buffer
+    /// has wrapped around.
+    pub fn oldest(&self) -> Option<Transmission> {
+        self.live_oldest_first().next()
+    }
+
+    /// Every retained round as `(round_id, transmission)`, oldest first. Historical
+    /// samples that are still covered by the live buffer are skipped.
+    pub fn series(&self) -> Vec<(u32, Transmission)> {
+        let latest_round_id = self.header.latest_round_id;
+        let live_len = (self.live.len() as u32).min(latest_round_id);
+        let live_start = latest_round_id - live_len + 1;
+        let granularity = self.header.granularity as u32;
+
+        let mut series = Vec::with_capacity(live_len as usize + self.historical.len());
+        if granularity > 0 && !self.historical.is_empty() {
+            let historical_end = latest_round_id - (latest_round_id % granularity);
+            let historical_len = (self.historical.len() as u32).min(latest_round_id / granularity);
+            for back in (0..historical_len).rev() {
+                let round_id = historical_end - back * granularity;
+                if round_id >= live_start {
+                    break;
+                }
+                let index = (self.header.historical_cursor as usize + self.historical.len()
+                    - 1
+                    - back as usize)
+                    % self.historical.len();
+                series.push((round_id, self.historical[index]));
+            }
+        }
+        series.extend((live_start..).zip(self.live_oldest_first()));
+        series
+    }
+
+    /// populated live transmissions, oldest first
+    pub(crate) fn live_oldest_first(&self) -> impl DoubleEndedIterator<Item = Transmission> + '_ {
+        let len = self.live.len();
+        let populated = len.min(self.header.latest_round_id as usize);
+        let cursor = self.header.live_cursor as usize;
+        (0..populated)
+            .rev()
+            .map(move |back| self.live[(cursor + len - 1 - back) % len])
+    }
+
+    /// Iterates over the populated live transmissions, newest first.
+    pub fn live_iter(&self) -> impl Iterator<Item = Transmission> + '_ {
+        self.live_oldest_first().rev()
+    }
+
+    /// Returns true if any live round that should have been written is still
+    /// uninitialized. Round ids are implied by position, so a skipped write shows up as
+    /// a zeroed transmission within the populated part of the buffer.
+    pub fn has_round_gaps(&self) -> bool {
+        self.live_oldest_first()
+            .any(|round| round == Transmission::default())
+    }
+
+    /// Iterates over the populated historical samples, oldest first.
+    pub fn historical_iter(&self) -> impl DoubleEndedIterator<Item = Transmission> + '_ {
+        let len = self.historical.len();
+        let populated = match self
+            .header
+            .latest_round_id
+            .checked_div(self.header.granularity as u32)
+        {
+            Some(samples) => len.min(samples as usize),
+            None => 0,
+        };
+        let cursor = self.header.historical_cursor as usize;
+        (0..populated)
+            .rev()
+            .map(move |back| self.historical[(cursor + len - 1 - back) % len])
+    }
+
+    /// Returns the most recent transmission with a timestamp at or before `unix_ts`,
+    /// searching the live buffer first and then the historical samples.
+    pub fn round_at_or_before(&self, unix_ts: u32) -> Option<Transmission> {
+        self.indexed_round_at_or_before(unix_ts)
+            .map(|(_, round)| round)
+    }
+
+    /// [`Feed::round_at_or_before`] along with the round id of the transmission
+    pub(crate) fn indexed_round_at_or_before(&self, unix_ts: u32) -> Option<(u32, Transmission)> {
+        let latest_round_id = self.header.latest_round_id;
+        let granularity = self.header.granularity as u32;
+        let historical_end = match latest_round_id.checked_rem(granularity) {
+            Some(remainder) => latest_round_id - remainder,
+            None => 0,
+        };
+        let live = self
+            .live_iter()
+            .zip(0..)
+            .map(|(round, back)| (latest_round_id - back, round));
+        let historical = self
+            .historical_iter()
+            .rev()
+            .zip(0..)
+            .map(|(round, back)| (historical_end - back * granularity, round));
+        live.chain(historical)
+            .find(|(_, round)| round.timestamp <= unix_ts)
+    }
+
+    /// Returns the largest absolute move in basis points between two consecutive live
+    /// rounds, skipping uninitialized rounds. Returns None if there are fewer than two
+    /// rounds or the math overflows.
+    pub fn max_single_round_move_bps(&self) -> Option<i64> {
+        let mut max = None;
+        let mut previous: Option<Transmission> = None;
+        for round in self
+            .live_oldest_first()
+            .filter(|round| *round != Transmission::default())
+        {
+            if let Some(previous) = previous.filter(|previous| previous.answer != 0) {
+                let bps = round
+                    .answer
+                    .checked_sub(previous.answer)?
+                    .checked_mul(10_000)?
+                    .checked_div(previous.answer)?
+                    .checked_abs()?;
+                max = max.max(Some(bps));
+            }
+            previous = Some(round);
+        }
+        max.and_then(|max| i64::try_from(max).ok())
+    }
+
+    /// Returns how many historical samples span `duration_secs`, assuming the nominal
+    /// cadence of one round per second, clamped to the historical buffer length.
+    pub fn historical_rounds_for_duration(&self, duration_secs: u32) -> u32 {
+        match duration_secs.checked_div(self.header.granularity as u32) {
+            Some(rounds) => rounds.min(self.historical.len() as u32),
+            None => 0,
+        }
+    }
+
+    /// Returns how many rounds remain until `latest_round_id` reaches the next multiple of
+    /// the granularity, at which point a historical sample is written. Returns 0 if the
+    /// latest round is already aligned, or if the granularity is zero.
+    pub fn rounds_until_next_historical(&self) -> u32 {
+        let granularity = self.header.granularity as u32;
+        match self.header.latest_round_id.checked_rem(granularity) {
+            Some(0) | None => 0,
+            Some(remainder) => granularity - remainder,
+        }
+    }
+
+    /// Returns the capacity of the historical buffer, derived from the account size.
+    pub fn historical_length(&self) -> usize {
+        self.historical.len()
+    }
+
+    /// Returns the transmission stored at a physical index of the historical buffer,
+    /// without any round id translation.
+    pub fn historical_at(&self, index: usize) -> Option<Transmission> {
+        self.historical.get(index).copied()
+    }
+
+    /// For feeds whose answer is a cumulative index, returns the simple (non compounded)
+    /// annualized rate in basis points implied by the index growth between two rounds,
+    /// assuming a 365 day year. Returns None if either round is unavailable, the
+    /// timestamps are equal, the `from` answer is zero or the math overflows.
+    pub fn implied_rate_bps(&self, from_round: u32, to_round: u32) -> Option<i64> {
+        let from = self.fetch(from_round)?;
+        let to = self.fetch(to_round)?;
+        let elapsed = i128::from(to.timestamp) - i128::from(from.timestamp);
+        if elapsed == 0 || from.answer == 0 {
+            return None;
+        }
+        let rate = to
+            .answer
+            .checked_sub(from.answer)?
+            .checked_mul(10_000)?
+            .checked_mul(SECONDS_PER_YEAR)?
+            .checked_div(from.answer.checked_mul(elapsed)?)?;
+        i64::try_from(rate).ok()
+    }
+
+    /// first round id held by the live buffer, past the latest round if it is empty
+    fn live_start(&self) -> u32 {
+        let latest_round_id = self.header.latest_round_id;
+        latest_round_id - (self.live.len() as u32).min(latest_round_id) + 1
+    }
+
+    /// Same as [`Feed::fetch`], but fails instead of rounding down to a historical sample
+    /// when the round isn't available at exact resolution.
+    pub fn fetch_exact(&self, round_id: u32) -> Result<Transmission, FetchError> {
+        let transmission = self.fetch(round_id).ok_or(FetchError::NotFound)?;
+        if round_id >= self.live_start() {
+            return Ok(transmission);
+        }
+        // the round was served from the historical samples, so the granularity is non zero
+        let returned_round_id = round_id - round_id % self.header.granularity as u32;
+        if returned_round_id != round_id {
+            return Err(FetchError::RoundedToHistorical { returned_round_id });
+        }
+        Ok(transmission)
+    }
+
     pub fn fetch(&self, round_id: u32) -> Option<Transmission> {
         if self.header.latest_round_id < round_id {
             return None;
@@ -158,11 +651,20 @@ impl<'a> Feed<'a> {
         let granularity = self.header.granularity as u32;
 
         // if in live range, fetch from live set
-        let live_start = latest_round_id.saturating_sub((self.live.len() as u32).saturating_sub(1));
-        // if in historical range, fetch from closest
-        let historical_end = latest_round_id - (latest_round_id % granularity);
-        let historical_start = historical_end
-            .saturating_sub(granularity * (self.historical.len() as u32).saturating_sub(1));
+        let live_start = self.live_start();
+        // if in historical range, fetch from closest. a granularity of zero only happens
+        // with corrupt data, in which case the historical samples can't be located. the
+        // range only covers populated samples, so round ids below the first one are missing
+        let historical_len = self.historical.len() as u32;
+        let (historical_start, historical_end) = match latest_round_id.checked_div(granularity) {
+            Some(samples) if samples > 0 && historical_len > 0 => {
+                let historical_end = latest_round_id - (latest_round_id % granularity);
+                let historical_start =
+                    historical_end - granularity * (samples.min(historical_len) - 1);
+                (historical_start, historical_end)
+            }
+            _ => (1, 0),
+        };
 
         if (live_start..=latest_round_id).contains(&round_id) {
             // live data
@@ -173,9 +675,9 @@ impl<'a> Feed<'a> {
                 .header
                 .live_cursor
                 .checked_sub(offset)
-                .unwrap_or(self.live.len() as u32 - (offset - self.header.live_cursor));
+                .unwrap_or_else(|| self.live.len() as u32 - (offset - self.header.live_cursor));
 
-            Some(self.live[index as usize])
+            self.live.get(index as usize).copied()
         } else if (historical_start..=historical_end).contains(&round_id) {
             // historical data
             let round_id = round_id - (round_id % granularity);
@@ -186,11 +688,11 @@ impl<'a> Feed<'a> {
                 .header
                 .historical_cursor
                 .checked_sub(offset)
-                .unwrap_or({
+                .unwrap_or_else(|| {
                     self.historical.len() as u32 - (offset - self.header.historical_cursor)
                 });
 
-            Some(self.historical[index as usize])
+            self.historical.get(index as usize).copied()
         } else {
             None
         }
@@ -199,7 +701,9 @@ impl<'a> Feed<'a> {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "client")]
     use solana_program::account_info::IntoAccountInfo;
+    #[cfg(feature = "client")]
     use static_pubkey::static_pubkey;
     use type_layout::TypeLayout;
 
@@ -209,6 +713,7 @@ mod tests {
         println!("{}", Transmissions::type_layout());
     }
     #[test]
+    #[cfg(feature = "client")]
     fn transmissions_btc() {
         let rpc = solana_client::rpc_client::RpcClient::new("https://ssc-dao.genesysgo.net");
         let btc_feed = static_pubkey!("CGmWwBNsTRDENT5gmVZzRu38GnNnMm1K5C3sFiUUyYQX");
@@ -219,11 +724,102 @@ mod tests {
             assert_eq!(feed.header.live_length, 86400);
         })
         .unwrap();
-        let latest_round_id = to_u32(&AccessorType::U32(143).access(&btc_feed_info)[..]);
+        let latest_round_id = read_header_raw(&btc_feed_info).unwrap().latest_round_id();
         // latest round as of jul 15th
         assert!(latest_round_id >= 2176986);
     }
     #[test]
+    fn store_accessors() {
+        let (owner, proposed_owner, controller) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut data = Store::DISCRIMINATOR.to_vec();
+        for pubkey in [owner, proposed_owner, controller] {
+            data.extend_from_slice(pubkey.as_ref());
+        }
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::CHAINLINK_STORE_PROGRAM,
+            false,
+            0,
+        );
+        assert_eq!(read_store_owner(&info).unwrap(), owner);
+        assert_eq!(read_proposed_owner(&info).unwrap(), proposed_owner);
+        assert_eq!(read_lowering_access_controller(&info).unwrap(), controller);
+
+        info.data.borrow_mut()[0] ^= 1;
+        assert_eq!(
+            read_store_owner(&info).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        info.data.borrow_mut()[0] ^= 1;
+        let mut truncated = info.data.borrow()[..Store::LEN - 1].to_vec();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut truncated,
+            &crate::CHAINLINK_STORE_PROGRAM,
+            false,
+            0,
+        );
+        assert_eq!(
+            read_store_owner(&info).unwrap_err(),
+            SclinkError::TruncatedAccount.into()
+        );
+    }
+    #[test]
+    fn header_ref_matches_borsh() {
+        let mut data = crate::test_utils::FeedBuilder::new()
+            .decimals(6)
+            .description("ETH / USD")
+            .insert_round(Transmission::new(1, 1, 1))
+            .insert_round(Transmission::new(2, 2, 2))
+            .build();
+        // the builder leaves the keys zeroed, give every field a distinct value so a
+        // wrong offset can't go unnoticed
+        let mut header = read_header(&data).unwrap();
+        header._discriminator = [1, 2, 3, 4, 5, 6, 7, 8];
+        header.state = Transmissions::FLAGGED;
+        header.owner = Pubkey::new_unique();
+        header.proposed_owner = Pubkey::new_unique();
+        header.writer = Pubkey::new_unique();
+        header.flagging_threshold = 0x0102_0304;
+        header.serialize(&mut &mut data[..]).unwrap();
+
+        let raw = header_ref(&data).unwrap();
+        let header = read_header(&data).unwrap();
+        assert_eq!(raw._discriminator, header._discriminator);
+        assert_eq!(raw.version, header.version);
+        assert_eq!(raw.state, header.state);
+        assert_eq!(raw.owner, header.owner);
+        assert_eq!(raw.proposed_owner, header.proposed_owner);
+        assert_eq!(raw.writer, header.writer);
+        assert_eq!(raw.description, header.description);
+        assert_eq!(raw.decimals, header.decimals);
+        assert_eq!(raw.flagging_threshold(), header.flagging_threshold);
+        assert_eq!(raw.latest_round_id(), header.latest_round_id);
+        assert_eq!(raw.latest_round_id(), 2);
+        assert_eq!(raw.granularity, header.granularity);
+        assert_eq!(raw.live_length(), header.live_length);
+        assert_eq!(raw.live_cursor(), header.live_cursor);
+        assert_eq!(raw.historical_cursor(), header.historical_cursor);
+        assert_eq!(
+            header_ref(&data[..8 + HEADER_SIZE - 1]).unwrap_err(),
+            SclinkError::TruncatedAccount.into()
+        );
+    }
+    #[test]
     fn transmissions() {
         let live_length = 2;
         let historical_length = 3;
@@ -271,74 +867,461 @@ mod tests {
 
         with_store(&info, |store| {
             for i in 1..=20 {
-                store.insert(Transmission {
-                    slot: u64::from(i),
-                    answer: i128::from(i),
-                    timestamp: i,
-                    ..Default::default()
-                });
+                store
+                    .insert(Transmission::new(u64::from(i), i, i128::from(i)))
+                    .unwrap();
             }
 
             assert_eq!(store.fetch(21), None);
             // Live range returns precise round
-            assert_eq!(
-                store.fetch(20),
-                Some(Transmission {
-                    slot: 20,
-                    answer: 20,
-                    timestamp: 20,
-                    ..Default::default()
-                })
-            );
-            assert_eq!(
-                store.fetch(19),
-                Some(Transmission {
-                    slot: 19,
-                    answer: 19,
-                    timestamp: 19,
-                    ..Default::default()
-                })
-            );
+            assert_eq!(store.fetch(20), Some(Transmission::new(20, 20, 20)));
+            assert_eq!(store.fetch(19), Some(Transmission::new(19, 19, 19)));
             // Historical range rounds down
-            assert_eq!(
-                store.fetch(18),
-                Some(Transmission {
-                    slot: 15,
-                    answer: 15,
-                    timestamp: 15,
-                    ..Default::default()
-                })
-            );
-            assert_eq!(
-                store.fetch(15),
-                Some(Transmission {
-                    slot: 15,
-                    answer: 15,
-                    timestamp: 15,
-                    ..Default::default()
-                })
-            );
-            assert_eq!(
-                store.fetch(14),
-                Some(Transmission {
-                    slot: 10,
-                    answer: 10,
-                    timestamp: 10,
-                    ..Default::default()
-                })
-            );
-            assert_eq!(
-                store.fetch(10),
-                Some(Transmission {
-                    slot: 10,
-                    answer: 10,
-                    timestamp: 10,
-                    ..Default::default()
-                })
-            );
+            assert_eq!(store.fetch(18), Some(Transmission::new(15, 15, 15)));
+            assert_eq!(store.fetch(15), Some(Transmission::new(15, 15, 15)));
+            assert_eq!(store.fetch(14), Some(Transmission::new(10, 10, 10)));
+            assert_eq!(store.fetch(10), Some(Transmission::new(10, 10, 10)));
             // Out of range
             assert_eq!(store.fetch(9), None);
         })
         .unwrap();
     }
+    fn header(granularity: u8, live_length: u32) -> Transmissions {
+        Transmissions {
+            _discriminator: [0_u8; 8],
+            version: 2,
+            state: Transmissions::NORMAL,
+            owner: Pubkey::default(),
+            proposed_owner: Pubkey::default(),
+            writer: Pubkey::default(),
+            description: [0; 32],
+            decimals: 18,
+            flagging_threshold: 1000,
+            latest_round_id: 0,
+            granularity,
+            live_length,
+            live_cursor: 0,
+            historical_cursor: 0,
+        }
+    }
+    fn feed(header: &mut Transmissions, historical_length: usize) -> Feed<'_> {
+        Feed {
+            live: Cow::Owned(vec![Transmission::default(); header.live_length as usize]),
+            historical: Cow::Owned(vec![Transmission::default(); historical_length]),
+            header,
+        }
+    }
+    fn insert_rounds(feed: &mut Feed, rounds: std::ops::RangeInclusive<u32>) {
+        for i in rounds {
+            feed.insert(Transmission::new(u64::from(i), i, i128::from(i)))
+                .unwrap();
+        }
+    }
+    #[test]
+    fn series() {
+        let mut header = header(5, 2);
+        let mut feed = feed(&mut header, 3);
+        assert!(feed.series().is_empty());
+        insert_rounds(&mut feed, 1..=20);
+        // historical samples 10 and 15 precede the live rounds, 20 is already live
+        let series = feed.series();
+        assert_eq!(
+            series
+                .iter()
+                .map(|(round_id, _)| *round_id)
+                .collect::<Vec<_>>(),
+            vec![10, 15, 19, 20]
+        );
+        for (round_id, transmission) in series {
+            assert_eq!(transmission.answer, i128::from(round_id));
+        }
+    }
+    #[test]
+    fn historical_at() {
+        let mut header = header(5, 2);
+        let mut feed = feed(&mut header, 3);
+        insert_rounds(&mut feed, 1..=20);
+        // rounds 5, 10, 15 filled the buffer, 20 wrapped around to index 0
+        assert_eq!(feed.historical_at(0).unwrap().answer, 20);
+        assert_eq!(feed.historical_at(2).unwrap().answer, 15);
+        assert_eq!(feed.historical_at(3), None);
+    }
+    #[test]
+    fn implied_rate_bps() {
+        let mut header = header(5, 10);
+        let mut feed = feed(&mut header, 3);
+        let half_year = (SECONDS_PER_YEAR / 2) as u32;
+        for (timestamp, answer) in [(1_000, 1_000_000), (1_000 + half_year, 1_010_000)] {
+            feed.insert(Transmission {
+                timestamp,
+                answer,
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        // 1% over half a year is 2% annualized
+        assert_eq!(feed.implied_rate_bps(1, 2), Some(200));
+        assert_eq!(feed.implied_rate_bps(2, 1), Some(198));
+        assert_eq!(feed.implied_rate_bps(1, 1), None);
+        assert_eq!(feed.implied_rate_bps(1, 3), None);
+    }
+    #[test]
+    fn max_single_round_move_bps() {
+        let mut header = header(5, 10);
+        let mut feed = feed(&mut header, 3);
+        feed.insert(Transmission::default()).unwrap();
+        assert_eq!(feed.max_single_round_move_bps(), None);
+        for (i, answer) in [100, 101, 90, 95].into_iter().enumerate() {
+            feed.insert(Transmission {
+                timestamp: i as u32 + 1,
+                answer,
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        // 101 -> 90 is the largest move, the uninitialized first round is skipped
+        assert_eq!(feed.max_single_round_move_bps(), Some(1089));
+    }
+    #[test]
+    fn with_store_malformed_length() {
+        // one live transmission plus a truncated trailing one
+        let mut data = vec![0; 8 + HEADER_SIZE + size_of::<Transmission>() + 10];
+        header(5, 1).serialize(&mut &mut data[..]).unwrap();
+
+        let mut lamports = 0u64;
+        let pubkey = Pubkey::default();
+        let info = AccountInfo::new(
+            &pubkey,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::CHAINLINK_STORE_PROGRAM,
+            false,
+            0,
+        );
+        assert_eq!(
+            with_store(&info, |_| ()).unwrap_err(),
+            SclinkError::TruncatedAccount.into()
+        );
+    }
+    #[test]
+    fn with_store_shorter_than_declared() {
+        // the header declares 4 live transmissions but only 2 follow it
+        let mut data = vec![0; 8 + HEADER_SIZE + 2 * size_of::<Transmission>()];
+        header(5, 4).serialize(&mut &mut data[..]).unwrap();
+        assert_eq!(
+            with_store_bytes(&data, |_| ()).unwrap_err(),
+            ProgramError::AccountDataTooSmall
+        );
+        let mut header = header(5, u32::MAX);
+        header.latest_round_id = 1;
+        header.serialize(&mut &mut data[..]).unwrap();
+        assert_eq!(
+            with_store_bytes(&data, |store| store.latest()).unwrap_err(),
+            ProgramError::AccountDataTooSmall
+        );
+    }
+    #[test]
+    fn with_store_empty_live_buffer() {
+        let mut data = vec![0; 8 + HEADER_SIZE + 3 * size_of::<Transmission>()];
+        let mut header = header(5, 0);
+        header.latest_round_id = 1;
+        header.serialize(&mut &mut data[..]).unwrap();
+        let mut lamports = 0u64;
+        let pubkey = Pubkey::default();
+        let info = AccountInfo::new(
+            &pubkey,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::CHAINLINK_STORE_PROGRAM,
+            false,
+            0,
+        );
+        // latest() would otherwise panic on the modulo by zero
+        assert_eq!(
+            with_store_readonly(&info, |store| store.latest()).unwrap_err(),
+            SclinkError::TruncatedAccount.into()
+        );
+    }
+    #[test]
+    fn has_round_gaps() {
+        let mut header = header(5, 4);
+        let mut feed = feed(&mut header, 3);
+        assert!(!feed.has_round_gaps());
+        insert_rounds(&mut feed, 1..=2);
+        // the two unwritten slots are outside of the populated region
+        assert!(!feed.has_round_gaps());
+        feed.insert(Transmission::default()).unwrap();
+        insert_rounds(&mut feed, 4..=6);
+        assert!(feed.has_round_gaps());
+        insert_rounds(&mut feed, 7..=7);
+        // the gap at round 3 has been overwritten
+        assert!(!feed.has_round_gaps());
+    }
+    #[test]
+    fn fetch_zero_granularity() {
+        let mut header = header(5, 2);
+        let mut feed = feed(&mut header, 3);
+        insert_rounds(&mut feed, 1..=20);
+        feed.header.granularity = 0;
+        assert_eq!(feed.fetch(20).unwrap().answer, 20);
+        assert_eq!(feed.fetch(15), None);
+        assert_eq!(feed.fetch(1), None);
+    }
+    #[test]
+    fn insert_overflow() {
+        let mut header = header(5, 2);
+        let mut feed = feed(&mut header, 3);
+        feed.header.latest_round_id = u32::MAX - 1;
+        insert_rounds(&mut feed, 1..=1);
+        assert_eq!(
+            feed.insert(Transmission::default()),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(feed.header.latest_round_id, u32::MAX);
+        assert_eq!(feed.latest().unwrap().answer, 1);
+    }
+    #[test]
+    fn insert_zero_granularity() {
+        let mut header = header(5, 2);
+        let mut feed = feed(&mut header, 3);
+        feed.header.granularity = 0;
+        insert_rounds(&mut feed, 1..=5);
+        assert_eq!(feed.header.historical_cursor, 0);
+        assert_eq!(feed.latest().unwrap().answer, 5);
+    }
+    #[test]
+    fn historical_rounds_for_duration() {
+        let mut header = header(5, 2);
+        let feed = feed(&mut header, 30);
+        assert_eq!(feed.historical_rounds_for_duration(60), 12);
+        assert_eq!(feed.historical_rounds_for_duration(64), 12);
+        assert_eq!(feed.historical_rounds_for_duration(3600), 30);
+        feed.header.granularity = 0;
+        assert_eq!(feed.historical_rounds_for_duration(60), 0);
+    }
+    #[test]
+    fn rounds_until_next_historical() {
+        let mut header = header(5, 2);
+        let feed = feed(&mut header, 3);
+        for (latest_round_id, expected) in [(0, 0), (1, 4), (3, 2), (4, 1), (5, 0), (7, 3)] {
+            feed.header.latest_round_id = latest_round_id;
+            assert_eq!(feed.rounds_until_next_historical(), expected);
+        }
+        feed.header.granularity = 0;
+        assert_eq!(feed.rounds_until_next_historical(), 0);
+    }
+    #[test]
+    fn historical_iter() {
+        let mut header = header(5, 2);
+        let mut feed = feed(&mut header, 3);
+        assert_eq!(feed.historical_iter().count(), 0);
+        insert_rounds(&mut feed, 1..=12);
+        assert_eq!(
+            feed.historical_iter()
+                .map(|round| round.answer)
+                .collect::<Vec<_>>(),
+            vec![5, 10]
+        );
+        insert_rounds(&mut feed, 13..=27);
+        // the buffer wrapped around, dropping 5 through 10
+        assert_eq!(
+            feed.historical_iter()
+                .map(|round| round.answer)
+                .collect::<Vec<_>>(),
+            vec![15, 20, 25]
+        );
+    }
+    #[test]
+    fn live_iter() {
+        let mut header = header(5, 4);
+        let mut feed = feed(&mut header, 3);
+        assert_eq!(feed.live_iter().count(), 0);
+        insert_rounds(&mut feed, 1..=2);
+        assert_eq!(
+            feed.live_iter()
+                .map(|round| round.answer)
+                .collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        insert_rounds(&mut feed, 3..=6);
+        assert_eq!(
+            feed.live_iter()
+                .map(|round| round.answer)
+                .collect::<Vec<_>>(),
+            vec![6, 5, 4, 3]
+        );
+    }
+    #[test]
+    fn round_at_or_before() {
+        let mut header = header(5, 2);
+        let mut feed = feed(&mut header, 3);
+        assert_eq!(feed.round_at_or_before(u32::MAX), None);
+        insert_rounds(&mut feed, 1..=12);
+        // live rounds 11 and 12
+        assert_eq!(feed.indexed_round_at_or_before(100).unwrap().0, 12);
+        assert_eq!(feed.indexed_round_at_or_before(11).unwrap().0, 11);
+        // falls back to historical samples 5 and 10
+        assert_eq!(feed.indexed_round_at_or_before(10).unwrap().0, 10);
+        assert_eq!(feed.round_at_or_before(9).unwrap().answer, 5);
+        assert_eq!(feed.round_at_or_before(4), None);
+    }
+    #[test]
+    fn latest_partially_filled() {
+        let mut header = header(5, 10);
+        let mut feed = feed(&mut header, 3);
+        assert_eq!(feed.latest(), None);
+        insert_rounds(&mut feed, 1..=3);
+        assert_eq!(feed.latest().unwrap().answer, 3);
+        insert_rounds(&mut feed, 4..=10);
+        assert_eq!(feed.latest().unwrap().answer, 10);
+        insert_rounds(&mut feed, 11..=11);
+        assert_eq!(feed.latest().unwrap().answer, 11);
+    }
+    #[test]
+    fn fetch_boundaries() {
+        let mut header = header(5, 2);
+        let mut feed = feed(&mut header, 3);
+        insert_rounds(&mut feed, 1..=12);
+        // live 11 and 12, historical 5 and 10
+        assert_eq!(feed.fetch(13), None);
+        assert_eq!(feed.fetch(12).unwrap().answer, 12);
+        assert_eq!(feed.fetch(11).unwrap().answer, 11);
+        assert_eq!(feed.fetch(10).unwrap().answer, 10);
+        assert_eq!(feed.fetch(9).unwrap().answer, 5);
+        assert_eq!(feed.fetch(5).unwrap().answer, 5);
+        // the historical buffer isn't full yet, nothing precedes the first sample
+        assert_eq!(feed.fetch(4), None);
+        assert_eq!(feed.fetch(0), None);
+
+        insert_rounds(&mut feed, 13..=27);
+        // live 26 and 27, historical 15, 20 and 25
+        assert_eq!(feed.fetch(26).unwrap().answer, 26);
+        assert_eq!(feed.fetch(25).unwrap().answer, 25);
+        assert_eq!(feed.fetch(15).unwrap().answer, 15);
+        assert_eq!(feed.fetch(14), None);
+    }
+    #[test]
+    fn oldest() {
+        let mut header = header(5, 4);
+        let mut feed = feed(&mut header, 3);
+        assert_eq!(feed.oldest(), None);
+        insert_rounds(&mut feed, 1..=3);
+        assert_eq!(feed.oldest().unwrap().answer, 1);
+        insert_rounds(&mut feed, 4..=6);
+        assert_eq!(feed.oldest().unwrap().answer, 3);
+    }
+    #[test]
+    fn fetch_exact() {
+        let mut header = header(5, 2);
+        let mut feed = feed(&mut header, 3);
+        insert_rounds(&mut feed, 1..=3);
+        assert_eq!(feed.fetch_exact(0), Err(FetchError::NotFound));
+        insert_rounds(&mut feed, 4..=12);
+        // live 11 and 12, historical 5 and 10
+        assert_eq!(feed.fetch_exact(11).unwrap().answer, 11);
+        assert_eq!(feed.fetch_exact(10).unwrap().answer, 10);
+        assert_eq!(
+            feed.fetch_exact(9),
+            Err(FetchError::RoundedToHistorical {
+                returned_round_id: 5
+            })
+        );
+        assert_eq!(feed.fetch_exact(13), Err(FetchError::NotFound));
+        assert_eq!(feed.fetch_exact(4), Err(FetchError::NotFound));
+    }
+    proptest::proptest! {
+        #[test]
+        fn fetch_consistent_with_insertion_order(
+            live_length in 1_u32..8,
+            historical_length in 1_usize..8,
+            granularity in 1_u8..6,
+            rounds in 0_u32..64,
+        ) {
+            let mut header = header(granularity, live_length);
+            let mut feed = feed(&mut header, historical_length);
+            insert_rounds(&mut feed, 1..=rounds);
+            let granularity = granularity as u32;
+            let live_start = rounds - live_length.min(rounds) + 1;
+            let samples = (rounds / granularity).min(historical_length as u32);
+            let historical_start = (rounds - rounds % granularity) - granularity * samples.saturating_sub(1);
+            for round_id in 0..=rounds + 1 {
+                let fetched = feed.fetch(round_id).map(|round| round.answer as u32);
+                if round_id == 0 || round_id > rounds {
+                    proptest::prop_assert_eq!(fetched, None);
+                } else if round_id >= live_start {
+                    proptest::prop_assert_eq!(fetched, Some(round_id));
+                } else if samples > 0 && round_id >= historical_start && round_id % granularity == 0 {
+                    proptest::prop_assert_eq!(fetched, Some(round_id));
+                } else if let Some(fetched) = fetched {
+                    // a historical sample at or before the requested round
+                    proptest::prop_assert!(fetched >= 1 && fetched <= round_id);
+                    proptest::prop_assert_eq!(fetched % granularity, 0);
+                    proptest::prop_assert!(round_id - fetched < granularity);
+                }
+            }
+        }
+    }
+    #[test]
+    fn empty_historical_buffer() {
+        let mut header = header(5, 2);
+        let mut feed = feed(&mut header, 0);
+        insert_rounds(&mut feed, 1..=12);
+        assert_eq!(feed.fetch(12).unwrap().answer, 12);
+        assert_eq!(feed.fetch(11).unwrap().answer, 11);
+        assert_eq!(feed.fetch(10), None);
+        assert_eq!(feed.fetch(5), None);
+        assert_eq!(feed.historical_iter().count(), 0);
+        assert_eq!(feed.historical_at(0), None);
+        assert_eq!(feed.round_at_or_before(10), None);
+        assert_eq!(
+            feed.series()
+                .iter()
+                .map(|(round_id, _)| *round_id)
+                .collect::<Vec<_>>(),
+            vec![11, 12]
+        );
+    }
+    #[test]
+    fn feed_view() {
+        let data = (1..=12).fold(
+            crate::test_utils::FeedBuilder::new()
+                .live_length(2)
+                .historical_length(3),
+            |builder, i| {
+                builder.insert_round(Transmission {
+                    answer: i,
+                    ..Default::default()
+                })
+            },
+        );
+        let mut data = data.build();
+        let mut lamports = 0u64;
+        let pubkey = Pubkey::default();
+        let info = AccountInfo::new(
+            &pubkey,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::CHAINLINK_STORE_PROGRAM,
+            false,
+            0,
+        );
+        let view = FeedView::load(&info).unwrap();
+        assert_eq!(view.latest_round_id, 12);
+        assert_eq!(view.decimals, 8);
+        let data = info.data.borrow();
+        assert_eq!(view.latest(&data).unwrap().unwrap().answer, 12);
+        assert_eq!(view.fetch(&data, 11).unwrap().unwrap().answer, 11);
+        assert_eq!(view.fetch(&data, 7).unwrap().unwrap().answer, 5);
+        assert_eq!(view.fetch(&data, 13).unwrap(), None);
+        assert_eq!(
+            view.fetch(&data[..100], 11).unwrap_err(),
+            SclinkError::TruncatedAccount.into()
+        );
+    }
 }
@@ -0,0 +1,100 @@
+//! push style access to a feed, polling it over rpc and yielding each new round once
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::stream::{self, Stream};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_program::pubkey::Pubkey;
+use tokio::time::{self as tokio_time, Interval, MissedTickBehavior};
+
+use crate::client::{decode, FeedClientError};
+use crate::Round;
+
+/// Polls `feed` every `interval` and yields its latest round whenever the round id
+/// changes. The first poll happens immediately. Failed polls are yielded as errors
+/// without ending the stream, so callers decide whether to keep polling.
+pub fn latest_round_stream(
+    rpc: Arc<RpcClient>,
+    feed: Pubkey,
+    interval: Duration,
+) -> impl Stream<Item = Result<Round, FeedClientError>> {
+    // the interval needs a running tokio runtime, so it's created on the first poll
+    let state: (Option<Interval>, Option<u32>) = (None, None);
+    stream::unfold(state, move |(ticker, mut last_round_id)| {
+        let rpc = rpc.clone();
+        async move {
+            let mut ticker = ticker.unwrap_or_else(|| {
+                let mut ticker = tokio_time::interval(interval);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                ticker
+            });
+            loop {
+                ticker.tick().await;
+                let round = match rpc.get_account(&feed).await {
+                    Ok(account) => decode(feed, account, crate::latest_round_data),
+                    Err(err) => Err(err.into()),
+                };
+                match round {
+                    Ok(round) if last_round_id == Some(round.round_id) => continue,
+                    Ok(round) => {
+                        last_round_id = Some(round.round_id);
+                        return Some((Ok(round), (Some(ticker), last_round_id)));
+                    }
+                    Err(err) => return Some((Err(err), (Some(ticker), last_round_id))),
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::Transmission;
+    use crate::test_utils::FeedBuilder;
+    use crate::CHAINLINK_STORE_PROGRAM;
+    use futures_util::StreamExt;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::bs58;
+
+    #[tokio::test]
+    async fn yields_rounds_and_errors() {
+        let data = FeedBuilder::new()
+            .insert_round(Transmission::new(1, 1, 42))
+            .build();
+        // the mock answers the first request only, later polls fail with AccountNotFound
+        let mocks = [(
+            RpcRequest::GetAccountInfo,
+            serde_json::json!({
+                "context": { "slot": 1 },
+                "value": {
+                    "lamports": 1,
+                    "data": bs58::encode(&data).into_string(),
+                    "owner": CHAINLINK_STORE_PROGRAM.to_string(),
+                    "executable": false,
+                    "rentEpoch": 0,
+                },
+            }),
+        )]
+        .into_iter()
+        .collect();
+        let rpc = Arc::new(RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            mocks,
+        ));
+        let polled: Vec<_> =
+            latest_round_stream(rpc, Pubkey::new_unique(), Duration::from_millis(1))
+                .take(2)
+                .collect()
+                .await;
+        assert!(matches!(
+            polled[0],
+            Ok(Round {
+                round_id: 1,
+                answer: 42,
+                ..
+            })
+        ));
+        assert!(matches!(polled[1], Err(FeedClientError::Rpc(_))));
+    }
+}
@@ -0,0 +1,107 @@
+//! helpers for building synthetic feed accounts in tests
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+
+use crate::store::{Transmission, Transmissions, HEADER_SIZE};
+use crate::FEED_VERSION;
+
+/// Builds the account data of a feed the same way the store program would, by
+/// inserting rounds into the live and historical ring buffers in order.
+///
+/// Defaults to 8 decimals, a granularity of 5, 10 live and 10 historical rounds.
+pub struct FeedBuilder {
+    decimals: u8,
+    granularity: u8,
+    live_length: usize,
+    historical_length: usize,
+    description: [u8; 32],
+    rounds: Vec<Transmission>,
+}
+
+impl Default for FeedBuilder {
+    fn default() -> Self {
+        FeedBuilder {
+            decimals: 8,
+            granularity: 5,
+            live_length: 10,
+            historical_length: 10,
+            description: [0; 32],
+            rounds: Vec::new(),
+        }
+    }
+}
+
+impl FeedBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn decimals(mut self, decimals: u8) -> Self {
+        self.decimals = decimals;
+        self
+    }
+    pub fn granularity(mut self, granularity: u8) -> Self {
+        self.granularity = granularity;
+        self
+    }
+    pub fn live_length(mut self, live_length: usize) -> Self {
+        self.live_length = live_length;
+        self
+    }
+    pub fn historical_length(mut self, historical_length: usize) -> Self {
+        self.historical_length = historical_length;
+        self
+    }
+    /// panics if the description is longer than 32 bytes
+    pub fn description(mut self, description: &str) -> Self {
+        assert!(description.len() <= 32, "description exceeds 32 bytes");
+        self.description = [0; 32];
+        self.description[..description.len()].copy_from_slice(description.as_bytes());
+        self
+    }
+    pub fn insert_round(mut self, round: Transmission) -> Self {
+        self.rounds.push(round);
+        self
+    }
+    /// Returns the account data, ready to be wrapped in an `AccountInfo` owned by
+    /// [`crate::CHAINLINK_STORE_PROGRAM`].
+    pub fn build(&self) -> Vec<u8> {
+        let mut header = Transmissions {
+            _discriminator: [0_u8; 8],
+            version: FEED_VERSION,
+            state: Transmissions::NORMAL,
+            owner: Pubkey::default(),
+            proposed_owner: Pubkey::default(),
+            writer: Pubkey::default(),
+            description: self.description,
+            decimals: self.decimals,
+            flagging_threshold: 1000,
+            latest_round_id: 0,
+            granularity: self.granularity,
+            live_length: self.live_length as u32,
+            live_cursor: 0,
+            historical_cursor: 0,
+        };
+        let mut live = vec![Transmission::default(); self.live_length];
+        let mut historical = vec![Transmission::default(); self.historical_length];
+        for round in &self.rounds {
+            header.latest_round_id += 1;
+            live[header.live_cursor as usize] = *round;
+            header.live_cursor = (header.live_cursor + 1) % self.live_length as u32;
+            if header
+                .latest_round_id
+                .checked_rem(u32::from(self.granularity))
+                == Some(0)
+                && !historical.is_empty()
+            {
+                historical[header.historical_cursor as usize] = *round;
+                header.historical_cursor =
+                    (header.historical_cursor + 1) % self.historical_length as u32;
+            }
+        }
+        let mut data = vec![0; 8 + HEADER_SIZE];
+        header.serialize(&mut &mut data[..]).unwrap();
+        data.extend_from_slice(bytemuck::cast_slice(&live));
+        data.extend_from_slice(bytemuck::cast_slice(&historical));
+        data
+    }
+}
@@ -0,0 +1,12 @@
+//! browser bindings decoding feed account data fetched with web3.js, without going
+//! through an rpc server
+use wasm_bindgen::prelude::*;
+
+/// Decodes the latest round from raw feed account data, returning an object with
+/// `round_id`, `slot`, `timestamp` and `answer`. The answer is a string as it may not
+/// fit a javascript number.
+#[wasm_bindgen]
+pub fn decode_latest_round(data: &[u8]) -> Result<JsValue, JsError> {
+    let round = crate::latest_round_from_bytes(data)?;
+    Ok(serde_wasm_bindgen::to_value(&round)?)
+}
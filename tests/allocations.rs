@@ -0,0 +1,95 @@
+//! Counts the heap allocations of the latest round paths, so that
+//! `latest_round_data_with_decimals2` can't regress into allocating more than the buffer
+//! it returns, or more than `latest_round_data_with_decimals`.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use borsh::BorshSerialize;
+use sclink::store::{Transmission, Transmissions, HEADER_SIZE};
+use solana_program::account_info::AccountInfo;
+use solana_program::pubkey::Pubkey;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // try_with as the thread local may already be destroyed during thread teardown
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// returns how many allocations `f` made on the current thread
+fn count_allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    std::hint::black_box(f());
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn with_decimals2_only_allocates_the_result() {
+    // a single round in a live buffer of two, without historical samples
+    let header = Transmissions {
+        _discriminator: [0; 8],
+        version: sclink::FEED_VERSION,
+        state: Transmissions::NORMAL,
+        owner: Pubkey::default(),
+        proposed_owner: Pubkey::default(),
+        writer: Pubkey::default(),
+        description: [0; 32],
+        decimals: 8,
+        flagging_threshold: 1000,
+        latest_round_id: 1,
+        granularity: 5,
+        live_length: 2,
+        live_cursor: 1,
+        historical_cursor: 0,
+    };
+    let mut data = vec![0; 8 + HEADER_SIZE];
+    header.serialize(&mut &mut data[..]).unwrap();
+    data.extend_from_slice(bytemuck::cast_slice(&[
+        Transmission::new(1, 1, 42),
+        Transmission::default(),
+    ]));
+    // on chain the transmissions are aligned, so offset the data by 8 bytes to have them
+    // start on a 16 byte boundary here as well
+    let mut buffer = vec![0_u128; (data.len() + 8) / 16 + 1];
+    let bytes = &mut bytemuck::cast_slice_mut::<_, u8>(&mut buffer)[8..8 + data.len()];
+    bytes.copy_from_slice(&data);
+
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let feed = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        bytes,
+        &sclink::CHAINLINK_STORE_PROGRAM,
+        false,
+        0,
+    );
+
+    let with_decimals =
+        count_allocations(|| sclink::latest_round_data_with_decimals(&feed).unwrap());
+    let with_decimals2 =
+        count_allocations(|| sclink::latest_round_data_with_decimals2(&feed).unwrap());
+    // the header fields are read in place, only the serialized round is allocated
+    assert_eq!(with_decimals2, 1);
+    assert!(
+        with_decimals2 <= with_decimals,
+        "with_decimals2 made {} allocations, with_decimals {}",
+        with_decimals2,
+        with_decimals
+    );
+}